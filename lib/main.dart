@@ -1,3 +1,5 @@
+import 'dart:ui';
+
 import 'package:flutter/material.dart';
 import 'package:galaxi/src/backend/api.dart';
 import 'package:galaxi/src/pages/home_page.dart';
@@ -19,13 +21,31 @@ class GalaxiApp extends StatefulWidget {
 class _GalaxiAppState extends State<GalaxiApp> {
   bool _darkTheme = false;
   bool _isLoading = true;
+  late final AppLifecycleListener _lifecycleListener;
 
   @override
   void initState() {
     super.initState();
+    _lifecycleListener = AppLifecycleListener(onExitRequested: _onExitRequested);
     _loadTheme();
   }
 
+  @override
+  void dispose() {
+    _lifecycleListener.dispose();
+    super.dispose();
+  }
+
+  Future<AppExitResponse> _onExitRequested() async {
+    // Let the backend stop downloads and flush state before we go away
+    try {
+      await shutdown();
+    } catch (e) {
+      // Backend may already be gone
+    }
+    return AppExitResponse.exit;
+  }
+
   Future<void> _loadTheme() async {
     try {
       final dark = await getDarkTheme();
@@ -6,6 +6,10 @@ Future<String> greet(String name) async {
   return await backendClient.call<String>('greet', [name]);
 }
 
+Future<void> shutdown() async {
+  await backendClient.call<void>('shutdown');
+}
+
 // Authentication API
 String getLoginUrl() {
   // This is a sync function, so we'll return the value directly
@@ -51,6 +55,12 @@ Future<List<AccountDto>> getAllAccounts() async {
   return result.map((e) => AccountDto.fromJson(e as Map<String, dynamic>)).toList();
 }
 
+/// Fetch every account's avatar again; returns the updated accounts
+Future<List<AccountDto>> refreshAccountAvatars() async {
+  final result = await backendClient.call<List<dynamic>>('refreshAccountAvatars');
+  return result.map((e) => AccountDto.fromJson(e as Map<String, dynamic>)).toList();
+}
+
 Future<AccountDto?> getActiveAccount() async {
   final result = await backendClient.call<Map<String, dynamic>?>('getActiveAccount');
   return result != null ? AccountDto.fromJson(result) : null;
@@ -61,8 +71,21 @@ Future<AccountDto> addCurrentAccount({required String refreshToken}) async {
   return AccountDto.fromJson(result);
 }
 
-Future<bool> switchAccount({required String userId}) async {
-  return await backendClient.call<bool>('switchAccount', [userId]);
+Future<SwitchResultDto> switchAccount({required String userId}) async {
+  final result = await backendClient.call<Map<String, dynamic>>('switchAccount', [userId]);
+  return SwitchResultDto.fromJson(result);
+}
+
+/// Log in the stored active account. Returns null when there is nothing to
+/// restore; throws when the login expired and the user has to sign in again.
+Future<AccountDto?> tryAutoLogin() async {
+  final result = await backendClient.call<Map<String, dynamic>?>('tryAutoLogin');
+  return result != null ? AccountDto.fromJson(result) : null;
+}
+
+Future<SwitchResultDto> resumeSession() async {
+  final result = await backendClient.call<Map<String, dynamic>>('resumeSession');
+  return SwitchResultDto.fromJson(result);
 }
 
 Future<void> removeAccount(String userId) async {
@@ -75,6 +98,27 @@ Future<List<GameDto>> getLibrary() async {
   return result.map((e) => GameDto.fromJson(e as Map<String, dynamic>)).toList();
 }
 
+Future<LibraryPageDto> getLibraryPage(int page) async {
+  final result = await backendClient.call<Map<String, dynamic>>('getLibraryPage', [page]);
+  return LibraryPageDto.fromJson(result);
+}
+
+/// Add an owned game that's missing from the library by its GOG product id
+Future<GameDto> addGameById(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('addGameById', [gameId]);
+  return GameDto.fromJson(result);
+}
+
+Future<List<GameDto>> getLibraryFiltered(String platform) async {
+  final result = await backendClient.call<List<dynamic>>('getLibraryFiltered', [platform]);
+  return result.map((e) => GameDto.fromJson(e as Map<String, dynamic>)).toList();
+}
+
+Future<List<GameDto>> searchLibrary(String query, {int page = 1}) async {
+  final result = await backendClient.call<List<dynamic>>('searchLibrary', [query, page]);
+  return result.map((e) => GameDto.fromJson(e as Map<String, dynamic>)).toList();
+}
+
 // Config API
 Future<ConfigDto> getConfig() async {
   final result = await backendClient.call<Map<String, dynamic>>('getConfig');
@@ -134,6 +178,32 @@ Future<void> setShowHiddenGames(bool enabled) async {
   await backendClient.call<void>('setShowHiddenGames', [enabled]);
 }
 
+Future<void> setGameHidden(int gameId, bool hidden) async {
+  await backendClient.call<void>('setGameHidden', [gameId, hidden]);
+}
+
+Future<void> setGameFavorite(int gameId, bool favorite) async {
+  await backendClient.call<void>('setGameFavorite', [gameId, favorite]);
+}
+
+Future<void> addGameTag(int gameId, String tag) async {
+  await backendClient.call<void>('addGameTag', [gameId, tag]);
+}
+
+Future<void> removeGameTag(int gameId, String tag) async {
+  await backendClient.call<void>('removeGameTag', [gameId, tag]);
+}
+
+Future<List<String>> getTags(int gameId) async {
+  final result = await backendClient.call<List<dynamic>>('getTags', [gameId]);
+  return result.map((e) => e as String).toList();
+}
+
+Future<List<GameDto>> getGamesByTag(String tag) async {
+  final result = await backendClient.call<List<dynamic>>('getGamesByTag', [tag]);
+  return result.map((e) => GameDto.fromJson(e as Map<String, dynamic>)).toList();
+}
+
 Future<bool> getKeepInstallers() async {
   return await backendClient.call<bool>('getKeepInstallers');
 }
@@ -142,6 +212,277 @@ Future<void> setKeepInstallers({required bool enabled}) async {
   await backendClient.call<void>('setKeepInstallers', [enabled]);
 }
 
+Future<bool?> getGameKeepInstaller(int gameId) async {
+  return await backendClient.call<bool?>('getGameKeepInstaller', [gameId]);
+}
+
+Future<void> setGameKeepInstaller(int gameId, bool? keep) async {
+  await backendClient.call<void>('setGameKeepInstaller', [gameId, keep]);
+}
+
+Future<bool> getInteractiveInstall() async {
+  return await backendClient.call<bool>('getInteractiveInstall');
+}
+
+Future<void> setInteractiveInstall({required bool enabled}) async {
+  await backendClient.call<void>('setInteractiveInstall', [enabled]);
+}
+
+Future<void> setGameInteractiveInstall(int gameId, bool? enabled) async {
+  await backendClient.call<void>('setGameInteractiveInstall', [gameId, enabled]);
+}
+
+Future<List<String>> getGameLaunchArguments(int gameId) async {
+  final result = await backendClient.call<List<dynamic>>('getGameLaunchArguments', [gameId]);
+  return result.map((e) => e as String).toList();
+}
+
+Future<void> setGameLaunchArguments(int gameId, List<String> args) async {
+  await backendClient.call<void>('setGameLaunchArguments', [gameId, args]);
+}
+
+Future<List<String>> getGameInstallerArgs(int gameId) async {
+  final result = await backendClient.call<List<dynamic>>('getGameInstallerArgs', [gameId]);
+  return result.map((e) => e as String).toList();
+}
+
+Future<void> setGameInstallerArgs(int gameId, List<String> args) async {
+  await backendClient.call<void>('setGameInstallerArgs', [gameId, args]);
+}
+
+Future<String> getGameCompatibilityTool(int gameId) async {
+  return await backendClient.call<String>('getGameCompatibilityTool', [gameId]);
+}
+
+Future<void> setGameCompatibilityTool(int gameId, String tool) async {
+  await backendClient.call<void>('setGameCompatibilityTool', [gameId, tool]);
+}
+
+Future<String> getGameWinePrefix(int gameId) async {
+  return await backendClient.call<String>('getGameWinePrefix', [gameId]);
+}
+
+/// Keep the game's Wine prefix at [prefixPath]; '' goes back to the default
+Future<void> setGameWinePrefix(int gameId, String prefixPath) async {
+  await backendClient.call<void>('setGameWinePrefix', [gameId, prefixPath]);
+}
+
+/// 'linux' or 'windows', or '' to use the platform GOG lists the game under
+Future<String> getPreferredPlatform(int gameId) async {
+  return await backendClient.call<String>('getPreferredPlatform', [gameId]);
+}
+
+Future<void> setPreferredPlatform(int gameId, String platform) async {
+  await backendClient.call<void>('setPreferredPlatform', [gameId, platform]);
+}
+
+Future<bool> getGameUseGamescope(int gameId) async {
+  return await backendClient.call<bool>('getGameUseGamescope', [gameId]);
+}
+
+Future<void> setGameUseGamescope(int gameId, bool enabled) async {
+  await backendClient.call<void>('setGameUseGamescope', [gameId, enabled]);
+}
+
+Future<bool> getGameShowFps(int gameId) async {
+  return await backendClient.call<bool>('getGameShowFps', [gameId]);
+}
+
+Future<void> setGameShowFps(int gameId, bool enabled) async {
+  await backendClient.call<void>('setGameShowFps', [gameId, enabled]);
+}
+
+Future<String> getGameGamescopeOptions(int gameId) async {
+  return await backendClient.call<String>('getGameGamescopeOptions', [gameId]);
+}
+
+Future<void> setGameGamescopeOptions(int gameId, String options) async {
+  await backendClient.call<void>('setGameGamescopeOptions', [gameId, options]);
+}
+
+/// Whether the game can be launched now, with the reason when it can't
+Future<LaunchReadinessDto> canLaunch(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('canLaunch', [gameId]);
+  return LaunchReadinessDto.fromJson(result);
+}
+
+Future<LaunchDiagnosisDto> diagnoseLaunch(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('diagnoseLaunch', [gameId]);
+  return LaunchDiagnosisDto.fromJson(result);
+}
+
+Future<String> getDosboxExecutable() async {
+  return await backendClient.call<String>('getDosboxExecutable');
+}
+
+Future<void> setDosboxExecutable(String executable) async {
+  await backendClient.call<void>('setDosboxExecutable', [executable]);
+}
+
+Future<String> getScummvmExecutable() async {
+  return await backendClient.call<String>('getScummvmExecutable');
+}
+
+Future<void> setScummvmExecutable(String executable) async {
+  await backendClient.call<void>('setScummvmExecutable', [executable]);
+}
+
+Future<List<String>> getIgnoredBinaries() async {
+  final result = await backendClient.call<List<dynamic>>('getIgnoredBinaries');
+  return result.cast<String>();
+}
+
+Future<void> addIgnoredBinary(String pattern) async {
+  await backendClient.call<void>('addIgnoredBinary', [pattern]);
+}
+
+Future<void> removeIgnoredBinary(String pattern) async {
+  await backendClient.call<void>('removeIgnoredBinary', [pattern]);
+}
+
+Future<List<String>> listLaunchCandidates(int gameId) async {
+  final result = await backendClient.call<List<dynamic>>('listLaunchCandidates', [gameId]);
+  return result.cast<String>();
+}
+
+Future<String?> getGameLaunchTarget(int gameId) async {
+  return await backendClient.call<String?>('getGameLaunchTarget', [gameId]);
+}
+
+Future<void> setGameLaunchTarget(int gameId, String? target) async {
+  await backendClient.call<void>('setGameLaunchTarget', [gameId, target]);
+}
+
+Future<GameSettingsDto> getGameSettings(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('getGameSettings', [gameId]);
+  return GameSettingsDto.fromJson(result);
+}
+
+Future<void> setGameSettings(int gameId, GameSettingsDto settings) async {
+  await backendClient.call<void>('setGameSettings', [gameId, settings.toJson()]);
+}
+
+Future<Map<String, String>> getGameEnvironment(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('getGameEnvironment', [gameId]);
+  return result.map((key, value) => MapEntry(key, value as String));
+}
+
+Future<void> setGameEnvironment(int gameId, Map<String, String> environment) async {
+  await backendClient.call<void>('setGameEnvironment', [gameId, environment]);
+}
+
+Future<int> getMaxParallelDownloads() async {
+  return await backendClient.call<int>('getMaxParallelDownloads');
+}
+
+Future<void> setMaxParallelDownloads(int count) async {
+  await backendClient.call<void>('setMaxParallelDownloads', [count]);
+}
+
+Future<int> getRequestTimeout() async {
+  return await backendClient.call<int>('getRequestTimeout');
+}
+
+Future<void> setRequestTimeout(int seconds) async {
+  await backendClient.call<void>('setRequestTimeout', [seconds]);
+}
+
+Future<String?> getProxyUrl() async {
+  return await backendClient.call<String?>('getProxyUrl');
+}
+
+Future<void> setProxyUrl(String? proxyUrl) async {
+  await backendClient.call<void>('setProxyUrl', [proxyUrl]);
+}
+
+Future<int> getMaxDownloadRetries() async {
+  return await backendClient.call<int>('getMaxDownloadRetries');
+}
+
+Future<void> setMaxDownloadRetries(int retries) async {
+  await backendClient.call<void>('setMaxDownloadRetries', [retries]);
+}
+
+Future<String> getLogLevel() async {
+  return await backendClient.call<String>('getLogLevel');
+}
+
+/// Set the lowest level written to the log file: error, warn, info or debug
+Future<void> initLogging(String level) async {
+  await backendClient.call<void>('initLogging', [level]);
+}
+
+/// Log file to attach to bug reports
+Future<String> getLogFilePath() async {
+  return await backendClient.call<String>('getLogFilePath');
+}
+
+Future<int> getDownloadSegments() async {
+  return await backendClient.call<int>('getDownloadSegments');
+}
+
+Future<void> setDownloadSegments(int segments) async {
+  await backendClient.call<void>('setDownloadSegments', [segments]);
+}
+
+Future<bool> getVerifyDownloads() async {
+  return await backendClient.call<bool>('getVerifyDownloads');
+}
+
+Future<void> setVerifyDownloads({required bool enabled}) async {
+  await backendClient.call<void>('setVerifyDownloads', [enabled]);
+}
+
+Future<bool> getCreateApplicationsFile() async {
+  return await backendClient.call<bool>('getCreateApplicationsFile');
+}
+
+Future<void> setCreateApplicationsFile({required bool enabled}) async {
+  await backendClient.call<void>('setCreateApplicationsFile', [enabled]);
+}
+
+Future<bool> getInstallDirIncludeId() async {
+  return await backendClient.call<bool>('getInstallDirIncludeId');
+}
+
+Future<void> setInstallDirIncludeId({required bool enabled}) async {
+  await backendClient.call<void>('setInstallDirIncludeId', [enabled]);
+}
+
+Future<bool> getStayLoggedIn() async {
+  return await backendClient.call<bool>('getStayLoggedIn');
+}
+
+Future<void> setStayLoggedIn({required bool enabled}) async {
+  await backendClient.call<void>('setStayLoggedIn', [enabled]);
+}
+
+Future<bool> isOffline() async {
+  return await backendClient.call<bool>('isOffline');
+}
+
+Future<String> ensureThumbnail(int gameId) async {
+  return await backendClient.call<String>('ensureThumbnail', [gameId]);
+}
+
+/// Local thumbnail paths keyed by game id
+Future<Map<int, String>> ensureThumbnails({List<int>? gameIds}) async {
+  final result = await backendClient.call<Map<String, dynamic>>('ensureThumbnails', [gameIds]);
+  return result.map((id, path) => MapEntry(int.parse(id), path as String));
+}
+
+Future<String> createDesktopEntry(int gameId) async {
+  return await backendClient.call<String>('createDesktopEntry', [gameId]);
+}
+
+Future<bool> getSortInstalledFirst() async {
+  return await backendClient.call<bool>('getSortInstalledFirst');
+}
+
+Future<void> setSortInstalledFirst({required bool enabled}) async {
+  await backendClient.call<void>('setSortInstalledFirst', [enabled]);
+}
+
 Future<String> getWinePrefix() async {
   return await backendClient.call<String>('getWinePrefix');
 }
@@ -166,6 +507,14 @@ Future<void> setWineDebug({required bool enabled}) async {
   await backendClient.call<void>('setWineDebug', [enabled]);
 }
 
+Future<String> getWineDebugChannels() async {
+  return await backendClient.call<String>('getWineDebugChannels');
+}
+
+Future<void> setWineDebugChannels(String channels) async {
+  await backendClient.call<void>('setWineDebugChannels', [channels]);
+}
+
 Future<bool> getWineDisableNtsync() async {
   return await backendClient.call<bool>('getWineDisableNtsync');
 }
@@ -192,9 +541,44 @@ Future<int> scanForInstalledGames() async {
   return await backendClient.call<int>('scanForInstalledGames');
 }
 
+Future<MarkInstalledResultDto> markGameInstalled(int gameId, String installDir) async {
+  final result = await backendClient.call<Map<String, dynamic>>('markGameInstalled', [gameId, installDir]);
+  return MarkInstalledResultDto.fromJson(result);
+}
+
+Future<MinigalaxyImportDto> importFromMinigalaxy() async {
+  final result = await backendClient.call<Map<String, dynamic>>('importFromMinigalaxy');
+  return MinigalaxyImportDto.fromJson(result);
+}
+
 // Download API
-Future<String> startDownload({required int gameId}) async {
-  return await backendClient.call<String>('startDownload', [gameId]);
+Future<String> startDownload({required int gameId, int? dlcId, String? language}) async {
+  return await backendClient.call<String>('startDownload', [gameId, dlcId, language]);
+}
+
+Future<bool> checkForUpdate(int gameId) async {
+  return await backendClient.call<bool>('checkForUpdate', [gameId]);
+}
+
+Future<List<UpdateInfoDto>> checkAllUpdates() async {
+  final result = await backendClient.call<List<dynamic>>('checkAllUpdates', []);
+  return result.map((e) => UpdateInfoDto.fromJson(e as Map<String, dynamic>)).toList();
+}
+
+Future<List<String>> getAvailableLanguages(int gameId) async {
+  final result = await backendClient.call<List<dynamic>>('getAvailableLanguages', [gameId]);
+  return result.map((e) => e as String).toList();
+}
+
+/// Download languages of a game with the size of each in bytes
+Future<List<LanguageOptionDto>> getLanguageOptions(int gameId) async {
+  final result = await backendClient.call<List<dynamic>>('getLanguageOptions', [gameId]);
+  return result.map((e) => LanguageOptionDto.fromJson(e as Map<String, dynamic>)).toList();
+}
+
+Future<DownloadInfoDto> getDlcDownloadInfo(int gameId, int dlcId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('getDlcDownloadInfo', [gameId, dlcId]);
+  return DownloadInfoDto.fromJson(result);
 }
 
 Future<GameDto> downloadAndInstall(int gameId) async {
@@ -202,31 +586,125 @@ Future<GameDto> downloadAndInstall(int gameId) async {
   return GameDto.fromJson(result);
 }
 
+Future<int> getGameDownloadSize(int gameId) async {
+  return await backendClient.call<int>('getGameDownloadSize', [gameId]);
+}
+
+Future<Map<int, int>> getLibraryDownloadSizes() async {
+  final result = await backendClient.call<Map<String, dynamic>>('getLibraryDownloadSizes');
+  return result.map((key, value) => MapEntry(int.parse(key), value as int));
+}
+
+Future<InstallerCachePruneDto> pruneInstallerCache() async {
+  final result = await backendClient.call<Map<String, dynamic>>('pruneInstallerCache', []);
+  return InstallerCachePruneDto.fromJson(result);
+}
+
+Future<List<CachedInstallerDto>> listCachedInstallers() async {
+  final result = await backendClient.call<List<dynamic>>('listCachedInstallers');
+  return result.map((e) => CachedInstallerDto.fromJson(e as Map<String, dynamic>)).toList();
+}
+
+Future<void> deleteCachedInstaller(String name) async {
+  await backendClient.call<void>('deleteCachedInstaller', [name]);
+}
+
 Future<void> pauseDownload(int gameId) async {
   await backendClient.call<void>('pauseDownload', [gameId]);
 }
 
+Future<String> resumeDownload(int gameId) async {
+  return await backendClient.call<String>('resumeDownload', [gameId]);
+}
+
+Future<PendingDownloadsDto> resumePendingDownloads() async {
+  final result = await backendClient.call<Map<String, dynamic>>('resumePendingDownloads');
+  return PendingDownloadsDto.fromJson(result);
+}
+
 Future<void> cancelDownload(int gameId) async {
   await backendClient.call<void>('cancelDownload', [gameId]);
 }
 
+Future<void> cancelAllDownloads() async {
+  await backendClient.call<void>('cancelAllDownloads');
+}
+
+/// Every change of any download's progress, pushed by the backend
+Stream<DownloadProgressDto> downloadProgressStream() {
+  return backendClient.events('/events/downloads').map(DownloadProgressDto.fromJson);
+}
+
 Future<DownloadProgressDto?> getDownloadProgress({required int gameId}) async {
   final result = await backendClient.call<Map<String, dynamic>?>('getDownloadProgress', [gameId]);
   return result != null ? DownloadProgressDto.fromJson(result) : null;
 }
 
+Future<List<DownloadProgressDto>> getActiveDownloads() async {
+  final result = await backendClient.call<List<dynamic>>('getActiveDownloads');
+  return result.map((e) => DownloadProgressDto.fromJson(e as Map<String, dynamic>)).toList();
+}
+
 // Installation API
-Future<GameDto> installGame({required int gameId, required String installerPath}) async {
+Future<InstallResultDto> installGame({required int gameId, required String installerPath}) async {
   final result = await backendClient.call<Map<String, dynamic>>('installGame', [gameId, installerPath]);
-  return GameDto.fromJson(result);
+  return InstallResultDto.fromJson(result);
 }
 
-Future<void> uninstallGame({required int gameId}) async {
-  await backendClient.call<void>('uninstallGame', [gameId]);
+Future<InstallResultDto> installFromFile({required int gameId, required String filePath}) async {
+  final result = await backendClient.call<Map<String, dynamic>>('installFromFile', [gameId, filePath]);
+  return InstallResultDto.fromJson(result);
 }
 
-Future<void> installDlc(int gameId, String dlcInstallerPath) async {
-  await backendClient.call<void>('installDlc', [gameId, dlcInstallerPath]);
+Future<InstalledVersionDto> getInstalledVersion(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('getInstalledVersion', [gameId]);
+  return InstalledVersionDto.fromJson(result);
+}
+
+/// Uninstall a game; returns warnings about files that were left behind
+Future<List<String>> uninstallGame({required int gameId}) async {
+  final result = await backendClient.call<List<dynamic>>('uninstallGame', [gameId]);
+  return result.cast<String>();
+}
+
+/// Uninstall after backing up the game's saves; returns the backup directory,
+/// empty when there was nothing to back up
+Future<String> uninstallGameKeepSaves({required int gameId}) async {
+  return await backendClient.call<String>('uninstallGameKeepSaves', [gameId]);
+}
+
+/// Put backed up saves back into the reinstalled game; returns the file count
+Future<int> restoreSaves({required int gameId}) async {
+  return await backendClient.call<int>('restoreSaves', [gameId]);
+}
+
+Future<void> installDlc(int gameId, String dlcInstallerPath, {int? dlcId}) async {
+  await backendClient.call<void>('installDlc', [gameId, dlcInstallerPath, dlcId]);
+}
+
+Future<MoveGameResultDto> moveGame(int gameId, String newBaseDir) async {
+  final result = await backendClient.call<Map<String, dynamic>>('moveGame', [gameId, newBaseDir]);
+  return MoveGameResultDto.fromJson(result);
+}
+
+Future<MoveProgressDto?> getMoveProgress(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>?>('getMoveProgress', [gameId]);
+  return result != null ? MoveProgressDto.fromJson(result) : null;
+}
+
+Future<VerifyReportDto> verifyInstall(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('verifyInstall', [gameId]);
+  return VerifyReportDto.fromJson(result);
+}
+
+Future<RepairResultDto> repairGame(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('repairGame', [gameId]);
+  return RepairResultDto.fromJson(result);
+}
+
+Future<InstallProgressDto?> getInstallProgress(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>?>('getInstallProgress', [gameId]);
+  return result != null ? InstallProgressDto.fromJson(result) : null;
 }
 
 // Wine Tools API
@@ -242,6 +720,19 @@ Future<void> openWinetricks(int gameId) async {
   await backendClient.call<void>('openWinetricks', [gameId]);
 }
 
+Future<void> openInstallDir(int gameId) async {
+  await backendClient.call<void>('openInstallDir', [gameId]);
+}
+
+Future<void> openWinePrefix(int gameId) async {
+  await backendClient.call<void>('openWinePrefix', [gameId]);
+}
+
+/// Returns the path the old prefix was moved to
+Future<String> resetWinePrefix(int gameId) async {
+  return await backendClient.call<String>('resetWinePrefix', [gameId]);
+}
+
 // Additional API functions
 Future<GameInfoDto> getGameInfo({required int gameId}) async {
   final result = await backendClient.call<Map<String, dynamic>>('getGameInfo', [gameId]);
@@ -253,20 +744,42 @@ Future<GamesDbInfoDto> getGamesdbInfo({required int gameId}) async {
   return GamesDbInfoDto.fromJson(result);
 }
 
+Future<GamesDbCachePathsDto> ensureCoverArt(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('ensureCoverArt', [gameId]);
+  return GamesDbCachePathsDto.fromJson(result);
+}
+
 Future<LaunchResultDto> launchGameById(int gameId) async {
   final result = await backendClient.call<Map<String, dynamic>>('launchGameById', [gameId]);
   return LaunchResultDto.fromJson(result);
 }
 
+Future<String?> getGameLogPath(int gameId) async {
+  return await backendClient.call<String?>('getGameLogPath', [gameId]);
+}
+
 Future<LaunchResultDto> launchGameAsync({required int gameId}) async {
   return await launchGameById(gameId);
 }
 
+// Backup API
+Future<void> exportDatabase(String destPath) async {
+  await backendClient.call<void>('exportDatabase', [destPath]);
+}
+
+Future<void> importDatabase(String srcPath) async {
+  await backendClient.call<void>('importDatabase', [srcPath]);
+}
+
 // Game Session Tracking API
 Future<bool> isGameRunning(int gameId) async {
   return await backendClient.call<bool>('isGameRunning', [gameId]);
 }
 
+Future<void> stopGame(int gameId) async {
+  await backendClient.call<void>('stopGame', [gameId]);
+}
+
 Future<int> getGamePlaytime(int gameId) async {
   return await backendClient.call<int>('getGamePlaytime', [gameId]);
 }
@@ -275,6 +788,11 @@ Future<int> getTotalGamePlaytime(int gameId) async {
   return await backendClient.call<int>('getTotalGamePlaytime', [gameId]);
 }
 
+Future<PlaytimeDto> getPlaytime(int gameId) async {
+  final result = await backendClient.call<Map<String, dynamic>>('getPlaytime', [gameId]);
+  return PlaytimeDto.fromJson(result);
+}
+
 Future<Map<String, dynamic>?> getRunningGame() async {
   return await backendClient.call<Map<String, dynamic>?>('getRunningGame');
 }
@@ -1,3 +1,4 @@
+import 'dart:async';
 import 'dart:convert';
 import 'package:http/http.dart' as http;
 
@@ -33,6 +34,34 @@ class BackendClient {
     }
   }
 
+  /// Server-sent events from [path], decoded as JSON. Cancelling the
+  /// subscription closes the connection.
+  Stream<Map<String, dynamic>> events(String path) {
+    final client = http.Client();
+    late final StreamController<Map<String, dynamic>> controller;
+    controller = StreamController<Map<String, dynamic>>(
+      onListen: () async {
+        try {
+          final response = await client.send(http.Request('GET', Uri.parse('$baseUrl$path')));
+          await for (final line in response.stream.transform(utf8.decoder).transform(const LineSplitter())) {
+            if (line.startsWith('data: ')) {
+              controller.add(jsonDecode(line.substring(6)) as Map<String, dynamic>);
+            }
+          }
+        } catch (e) {
+          if (!controller.isClosed) {
+            controller.addError(e);
+          }
+        }
+        if (!controller.isClosed) {
+          await controller.close();
+        }
+      },
+      onCancel: () => client.close(),
+    );
+    return controller.stream;
+  }
+
   void dispose() {
     _client.close();
   }
@@ -9,6 +9,9 @@ class GameDto {
   final String platform;
   final String category;
   final List<DlcDto> dlcs;
+  final bool hidden;
+  final bool favorite;
+  final List<String> tags;
 
   GameDto({
     required this.id,
@@ -19,6 +22,9 @@ class GameDto {
     required this.platform,
     required this.category,
     required this.dlcs,
+    this.hidden = false,
+    this.favorite = false,
+    this.tags = const [],
   });
 
   factory GameDto.fromJson(Map<String, dynamic> json) {
@@ -34,6 +40,9 @@ class GameDto {
               ?.map((e) => DlcDto.fromJson(e as Map<String, dynamic>))
               .toList() ??
           [],
+      hidden: json['hidden'] as bool? ?? false,
+      favorite: json['favorite'] as bool? ?? false,
+      tags: (json['tags'] as List?)?.map((e) => e as String).toList() ?? [],
     );
   }
 }
@@ -43,12 +52,16 @@ class DlcDto {
   final String name;
   final String title;
   final String imageUrl;
+  final bool installable;
+  final bool installed;
 
   DlcDto({
     required this.id,
     required this.name,
     required this.title,
     required this.imageUrl,
+    this.installable = true,
+    this.installed = false,
   });
 
   factory DlcDto.fromJson(Map<String, dynamic> json) {
@@ -57,6 +70,8 @@ class DlcDto {
       name: json['name'] as String,
       title: json['title'] as String,
       imageUrl: json['image_url'] as String,
+      installable: json['installable'] as bool? ?? true,
+      installed: json['installed'] as bool? ?? false,
     );
   }
 }
@@ -84,6 +99,26 @@ class AccountDto {
   }
 }
 
+class SwitchResultDto {
+  final bool success;
+  final bool needsReauth;
+  final bool offline;
+
+  SwitchResultDto({
+    required this.success,
+    required this.needsReauth,
+    this.offline = false,
+  });
+
+  factory SwitchResultDto.fromJson(Map<String, dynamic> json) {
+    return SwitchResultDto(
+      success: json['success'] as bool,
+      needsReauth: json['needs_reauth'] as bool,
+      offline: json['offline'] as bool? ?? false,
+    );
+  }
+}
+
 class UserDataDto {
   final String userId;
   final String username;
@@ -119,6 +154,8 @@ class ConfigDto {
   final bool wineDebug;
   final bool wineDisableNtsync;
   final bool wineAutoInstallDxvk;
+  final bool interactiveInstall;
+  final bool verifyDownloads;
 
   ConfigDto({
     required this.locale,
@@ -135,6 +172,8 @@ class ConfigDto {
     required this.wineDebug,
     required this.wineDisableNtsync,
     required this.wineAutoInstallDxvk,
+    this.interactiveInstall = false,
+    this.verifyDownloads = true,
   });
 
   factory ConfigDto.fromJson(Map<String, dynamic> json) {
@@ -153,6 +192,8 @@ class ConfigDto {
       wineDebug: json['wine_debug'] as bool,
       wineDisableNtsync: json['wine_disable_ntsync'] as bool,
       wineAutoInstallDxvk: json['wine_auto_install_dxvk'] as bool,
+      interactiveInstall: json['interactive_install'] as bool? ?? false,
+      verifyDownloads: json['verify_downloads'] as bool? ?? true,
     );
   }
 }
@@ -182,14 +223,18 @@ class GameInfoDto {
   final String title;
   final String? description;
   final String? changelog;
+  final String? changelogPlain;
   final List<String> screenshots;
+  final List<DlcDto> dlcs;
 
   GameInfoDto({
     required this.id,
     required this.title,
     this.description,
     this.changelog,
+    this.changelogPlain,
     required this.screenshots,
+    this.dlcs = const [],
   });
 
   factory GameInfoDto.fromJson(Map<String, dynamic> json) {
@@ -198,10 +243,192 @@ class GameInfoDto {
       title: json['title'] as String,
       description: json['description'] as String?,
       changelog: json['changelog'] as String?,
+      changelogPlain: json['changelog_plain'] as String?,
       screenshots: (json['screenshots'] as List?)
               ?.map((e) => e as String)
               .toList() ??
           [],
+      dlcs: (json['dlcs'] as List?)
+              ?.map((e) => DlcDto.fromJson(e as Map<String, dynamic>))
+              .toList() ??
+          [],
+    );
+  }
+}
+
+class PlaytimeDto {
+  final int gameId;
+  final int totalSeconds;
+  final DateTime? lastPlayed;
+
+  PlaytimeDto({
+    required this.gameId,
+    required this.totalSeconds,
+    this.lastPlayed,
+  });
+
+  factory PlaytimeDto.fromJson(Map<String, dynamic> json) {
+    final lastPlayed = json['last_played'] as String?;
+    return PlaytimeDto(
+      gameId: json['game_id'] as int,
+      totalSeconds: json['total_seconds'] as int,
+      lastPlayed: lastPlayed != null ? DateTime.tryParse(lastPlayed) : null,
+    );
+  }
+}
+
+class GameSettingsDto {
+  /// null follows the global setting
+  final bool? keepInstaller;
+  /// null follows the global setting
+  final bool? interactiveInstall;
+  final List<String> launchArguments;
+  final Map<String, String> environment;
+  final String compatibilityTool;
+  /// null autodetects the executable
+  final String? launchTarget;
+  final bool showFps;
+  final bool useGamescope;
+  final String gamescopeOptions;
+  final bool hidden;
+  final bool favorite;
+
+  GameSettingsDto({
+    this.keepInstaller,
+    this.interactiveInstall,
+    required this.launchArguments,
+    required this.environment,
+    required this.compatibilityTool,
+    this.launchTarget,
+    required this.showFps,
+    required this.useGamescope,
+    required this.gamescopeOptions,
+    required this.hidden,
+    this.favorite = false,
+  });
+
+  factory GameSettingsDto.fromJson(Map<String, dynamic> json) {
+    return GameSettingsDto(
+      keepInstaller: json['keep_installer'] as bool?,
+      interactiveInstall: json['interactive_install'] as bool?,
+      launchArguments: (json['launch_arguments'] as List<dynamic>).cast<String>(),
+      environment: (json['environment'] as Map<String, dynamic>).cast<String, String>(),
+      compatibilityTool: json['compatibility_tool'] as String,
+      launchTarget: json['launch_target'] as String?,
+      showFps: json['show_fps'] as bool? ?? false,
+      useGamescope: json['use_gamescope'] as bool,
+      gamescopeOptions: json['gamescope_options'] as String,
+      hidden: json['hidden'] as bool,
+      favorite: json['favorite'] as bool? ?? false,
+    );
+  }
+
+  Map<String, dynamic> toJson() {
+    return {
+      'keep_installer': keepInstaller,
+      'interactive_install': interactiveInstall,
+      'launch_arguments': launchArguments,
+      'environment': environment,
+      'compatibility_tool': compatibilityTool,
+      'launch_target': launchTarget,
+      'show_fps': showFps,
+      'use_gamescope': useGamescope,
+      'gamescope_options': gamescopeOptions,
+      'hidden': hidden,
+      'favorite': favorite,
+    };
+  }
+}
+
+class LaunchReadinessDto {
+  final bool ready;
+  final String? reason;
+
+  LaunchReadinessDto({required this.ready, this.reason});
+
+  factory LaunchReadinessDto.fromJson(Map<String, dynamic> json) {
+    return LaunchReadinessDto(
+      ready: json['ready'] as bool,
+      reason: json['reason'] as String?,
+    );
+  }
+}
+
+class LaunchDiagnosisDto {
+  final int gameId;
+  final String platform;
+  final String installDir;
+  final String? launcherType;
+  final List<String> command;
+  final String? cwd;
+  final Map<String, String> environment;
+  final String? winePath;
+  final Map<String, bool> directories;
+  final String? errorMessage;
+
+  LaunchDiagnosisDto({
+    required this.gameId,
+    required this.platform,
+    required this.installDir,
+    this.launcherType,
+    required this.command,
+    this.cwd,
+    required this.environment,
+    this.winePath,
+    required this.directories,
+    this.errorMessage,
+  });
+
+  factory LaunchDiagnosisDto.fromJson(Map<String, dynamic> json) {
+    return LaunchDiagnosisDto(
+      gameId: json['game_id'] as int,
+      platform: json['platform'] as String,
+      installDir: json['install_dir'] as String,
+      launcherType: json['launcher_type'] as String?,
+      command: (json['command'] as List<dynamic>).cast<String>(),
+      cwd: json['cwd'] as String?,
+      environment: (json['environment'] as Map<String, dynamic>).cast<String, String>(),
+      winePath: json['wine_path'] as String?,
+      directories: {
+        for (final dir in json['directories'] as List<dynamic>)
+          dir['path'] as String: dir['exists'] as bool,
+      },
+      errorMessage: json['error_message'] as String?,
+    );
+  }
+
+  /// Plain text report for pasting into a bug report
+  String toReport() {
+    return [
+      'Game: $gameId ($platform)',
+      'Install dir: $installDir',
+      'Launcher: ${launcherType ?? 'not detected'}',
+      if (command.isNotEmpty) 'Command: ${command.join(' ')}',
+      if (cwd != null) 'Working dir: $cwd',
+      if (winePath != null) 'Wine: $winePath',
+      for (final entry in environment.entries) 'Env: ${entry.key}=${entry.value}',
+      for (final entry in directories.entries) 'Dir: ${entry.key} (${entry.value ? 'exists' : 'missing'})',
+      if (errorMessage != null) 'Error: $errorMessage',
+    ].join('\n');
+  }
+}
+
+class GamesDbCachePathsDto {
+  final String? cover;
+  final String? verticalCover;
+  final String? background;
+
+  GamesDbCachePathsDto({
+    this.cover,
+    this.verticalCover,
+    this.background,
+  });
+
+  factory GamesDbCachePathsDto.fromJson(Map<String, dynamic> json) {
+    return GamesDbCachePathsDto(
+      cover: json['cover'] as String?,
+      verticalCover: json['vertical_cover'] as String?,
+      background: json['background'] as String?,
     );
   }
 }
@@ -239,6 +466,10 @@ class DownloadProgressDto {
   final int totalBytes;
   final int speedBytesPerSec;
   final String status;
+  final int? queuePosition;
+  final int currentFileIndex;
+  final int totalFiles;
+  final String? errorMessage;
 
   DownloadProgressDto({
     required this.gameId,
@@ -247,6 +478,10 @@ class DownloadProgressDto {
     required this.totalBytes,
     required this.speedBytesPerSec,
     required this.status,
+    this.queuePosition,
+    this.currentFileIndex = 0,
+    this.totalFiles = 0,
+    this.errorMessage,
   });
 
   factory DownloadProgressDto.fromJson(Map<String, dynamic> json) {
@@ -257,6 +492,340 @@ class DownloadProgressDto {
       totalBytes: json['total_bytes'] as int,
       speedBytesPerSec: json['speed_bytes_per_sec'] as int,
       status: json['status'] as String,
+      queuePosition: json['queue_position'] as int?,
+      currentFileIndex: json['current_file_index'] as int? ?? 0,
+      totalFiles: json['total_files'] as int? ?? 0,
+      errorMessage: json['error_message'] as String?,
+    );
+  }
+}
+
+class PendingDownloadsDto {
+  final List<int> resumed;
+  final List<int> paused;
+
+  PendingDownloadsDto({
+    required this.resumed,
+    required this.paused,
+  });
+
+  factory PendingDownloadsDto.fromJson(Map<String, dynamic> json) {
+    return PendingDownloadsDto(
+      resumed: (json['resumed'] as List?)?.map((e) => e as int).toList() ?? [],
+      paused: (json['paused'] as List?)?.map((e) => e as int).toList() ?? [],
+    );
+  }
+}
+
+class MarkInstalledResultDto {
+  final GameDto game;
+  final String? warning;
+
+  MarkInstalledResultDto({
+    required this.game,
+    this.warning,
+  });
+
+  factory MarkInstalledResultDto.fromJson(Map<String, dynamic> json) {
+    return MarkInstalledResultDto(
+      game: GameDto.fromJson(json['game'] as Map<String, dynamic>),
+      warning: json['warning'] as String?,
+    );
+  }
+}
+
+class MinigalaxyImportDto {
+  final bool found;
+  final List<String> settingsImported;
+  final int gameFilesImported;
+  final List<String> installedGamesRegistered;
+  final List<String> skipped;
+
+  MinigalaxyImportDto({
+    required this.found,
+    required this.settingsImported,
+    required this.gameFilesImported,
+    required this.installedGamesRegistered,
+    required this.skipped,
+  });
+
+  factory MinigalaxyImportDto.fromJson(Map<String, dynamic> json) {
+    return MinigalaxyImportDto(
+      found: json['found'] as bool,
+      settingsImported: (json['settings_imported'] as List?)?.map((e) => e as String).toList() ?? [],
+      gameFilesImported: json['game_files_imported'] as int,
+      installedGamesRegistered:
+          (json['installed_games_registered'] as List?)?.map((e) => e as String).toList() ?? [],
+      skipped: (json['skipped'] as List?)?.map((e) => e as String).toList() ?? [],
+    );
+  }
+}
+
+class InstallResultDto {
+  final GameDto game;
+  final String? installedVersion;
+  final String? installedLanguage;
+
+  InstallResultDto({
+    required this.game,
+    this.installedVersion,
+    this.installedLanguage,
+  });
+
+  factory InstallResultDto.fromJson(Map<String, dynamic> json) {
+    return InstallResultDto(
+      game: GameDto.fromJson(json),
+      installedVersion: json['installed_version'] as String?,
+      installedLanguage: json['installed_language'] as String?,
+    );
+  }
+}
+
+class InstallerCachePruneDto {
+  final List<String> removed;
+  final int freedBytes;
+
+  InstallerCachePruneDto({
+    required this.removed,
+    required this.freedBytes,
+  });
+
+  factory InstallerCachePruneDto.fromJson(Map<String, dynamic> json) {
+    return InstallerCachePruneDto(
+      removed: (json['removed'] as List?)?.map((e) => e as String).toList() ?? [],
+      freedBytes: json['freed_bytes'] as int,
+    );
+  }
+}
+
+class CachedInstallerDto {
+  final String name;
+  final int size;
+  final int? gameId;
+  final bool partial;
+
+  CachedInstallerDto({
+    required this.name,
+    required this.size,
+    this.gameId,
+    required this.partial,
+  });
+
+  factory CachedInstallerDto.fromJson(Map<String, dynamic> json) {
+    return CachedInstallerDto(
+      name: json['name'] as String,
+      size: json['size'] as int,
+      gameId: json['game_id'] as int?,
+      partial: json['partial'] as bool,
+    );
+  }
+}
+
+class LibraryPageDto {
+  final List<GameDto> games;
+  final int page;
+  final int totalPages;
+  final int totalCount;
+
+  LibraryPageDto({
+    required this.games,
+    required this.page,
+    required this.totalPages,
+    required this.totalCount,
+  });
+
+  factory LibraryPageDto.fromJson(Map<String, dynamic> json) {
+    return LibraryPageDto(
+      games: (json['games'] as List?)
+              ?.map((e) => GameDto.fromJson(e as Map<String, dynamic>))
+              .toList() ??
+          [],
+      page: json['page'] as int,
+      totalPages: json['total_pages'] as int,
+      totalCount: json['total_count'] as int,
+    );
+  }
+}
+
+class UpdateInfoDto {
+  final int gameId;
+  final String name;
+  final String installedVersion;
+  final String availableVersion;
+
+  UpdateInfoDto({
+    required this.gameId,
+    required this.name,
+    required this.installedVersion,
+    required this.availableVersion,
+  });
+
+  factory UpdateInfoDto.fromJson(Map<String, dynamic> json) {
+    return UpdateInfoDto(
+      gameId: json['game_id'] as int,
+      name: json['name'] as String,
+      installedVersion: json['installed_version'] as String,
+      availableVersion: json['available_version'] as String,
+    );
+  }
+}
+
+class InstalledVersionDto {
+  final String? version;
+  final String? language;
+
+  InstalledVersionDto({
+    this.version,
+    this.language,
+  });
+
+  factory InstalledVersionDto.fromJson(Map<String, dynamic> json) {
+    return InstalledVersionDto(
+      version: json['version'] as String?,
+      language: json['language'] as String?,
+    );
+  }
+}
+
+class MoveGameResultDto {
+  final GameDto game;
+  final List<String> warnings;
+
+  MoveGameResultDto({
+    required this.game,
+    required this.warnings,
+  });
+
+  factory MoveGameResultDto.fromJson(Map<String, dynamic> json) {
+    return MoveGameResultDto(
+      game: GameDto.fromJson(json['game'] as Map<String, dynamic>),
+      warnings: (json['warnings'] as List?)?.map((e) => e as String).toList() ?? [],
+    );
+  }
+}
+
+class MoveProgressDto {
+  final int gameId;
+  final int copiedBytes;
+  final int totalBytes;
+
+  MoveProgressDto({
+    required this.gameId,
+    required this.copiedBytes,
+    required this.totalBytes,
+  });
+
+  factory MoveProgressDto.fromJson(Map<String, dynamic> json) {
+    return MoveProgressDto(
+      gameId: json['game_id'] as int,
+      copiedBytes: json['copied_bytes'] as int,
+      totalBytes: json['total_bytes'] as int,
+    );
+  }
+}
+
+class VerifyReportDto {
+  final int gameId;
+  final int ok;
+  final List<String> missing;
+  final List<String> corrupt;
+
+  VerifyReportDto({
+    required this.gameId,
+    required this.ok,
+    required this.missing,
+    required this.corrupt,
+  });
+
+  factory VerifyReportDto.fromJson(Map<String, dynamic> json) {
+    return VerifyReportDto(
+      gameId: json['game_id'] as int,
+      ok: json['ok'] as int,
+      missing: (json['missing'] as List?)?.map((e) => e as String).toList() ?? [],
+      corrupt: (json['corrupt'] as List?)?.map((e) => e as String).toList() ?? [],
+    );
+  }
+}
+
+class RepairResultDto {
+  final GameDto game;
+  final int repaired;
+  final List<String> failed;
+  final bool reinstalled;
+
+  RepairResultDto({
+    required this.game,
+    required this.repaired,
+    required this.failed,
+    required this.reinstalled,
+  });
+
+  factory RepairResultDto.fromJson(Map<String, dynamic> json) {
+    return RepairResultDto(
+      game: GameDto.fromJson(json['game'] as Map<String, dynamic>),
+      repaired: json['repaired'] as int,
+      failed: (json['failed'] as List?)?.map((e) => e as String).toList() ?? [],
+      reinstalled: json['reinstalled'] as bool? ?? false,
+    );
+  }
+}
+
+class InstallProgressDto {
+  final int gameId;
+  final int? percent;
+  final int elapsedSecs;
+
+  InstallProgressDto({
+    required this.gameId,
+    this.percent,
+    required this.elapsedSecs,
+  });
+
+  factory InstallProgressDto.fromJson(Map<String, dynamic> json) {
+    return InstallProgressDto(
+      gameId: json['game_id'] as int,
+      percent: json['percent'] as int?,
+      elapsedSecs: json['elapsed_secs'] as int,
+    );
+  }
+}
+
+class LanguageOptionDto {
+  final String language;
+  final int totalSize;
+
+  LanguageOptionDto({required this.language, required this.totalSize});
+
+  factory LanguageOptionDto.fromJson(Map<String, dynamic> json) {
+    return LanguageOptionDto(
+      language: json['language'] as String,
+      totalSize: json['total_size'] as int,
+    );
+  }
+}
+
+class DownloadInfoDto {
+  final String os;
+  final String language;
+  final String? version;
+  final int totalSize;
+  final List<int> fileSizes;
+
+  DownloadInfoDto({
+    required this.os,
+    required this.language,
+    this.version,
+    required this.totalSize,
+    required this.fileSizes,
+  });
+
+  factory DownloadInfoDto.fromJson(Map<String, dynamic> json) {
+    return DownloadInfoDto(
+      os: json['os'] as String,
+      language: json['language'] as String,
+      version: json['version'] as String?,
+      totalSize: json['total_size'] as int,
+      fileSizes: (json['file_sizes'] as List?)?.map((e) => e as int).toList() ?? [],
     );
   }
 }
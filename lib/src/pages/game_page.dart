@@ -340,7 +340,18 @@ class _GamePageState extends State<GamePage> {
                       children: [
                         Expanded(
                           child: ElevatedButton.icon(
-                            onPressed: _isGameRunning ? null : () async {
+                            onPressed: _isGameRunning ? () async {
+                              try {
+                                await stopGame(widget.game.id);
+                                _checkGameRunning();
+                              } catch (e) {
+                                if (mounted) {
+                                  ScaffoldMessenger.of(context).showSnackBar(
+                                    SnackBar(content: Text('Failed to stop: $e')),
+                                  );
+                                }
+                              }
+                            } : () async {
                               try {
                                 await launchGameAsync(gameId: widget.game.id);
                                 // Start tracking playtime after launch
@@ -353,8 +364,8 @@ class _GamePageState extends State<GamePage> {
                                 }
                               }
                             },
-                            icon: Icon(_isGameRunning ? Icons.videogame_asset : Icons.play_arrow),
-                            label: Text(_isGameRunning ? 'Playing' : 'Play'),
+                            icon: Icon(_isGameRunning ? Icons.stop : Icons.play_arrow),
+                            label: Text(_isGameRunning ? 'Stop' : 'Play'),
                             style: ElevatedButton.styleFrom(
                               minimumSize: const Size(0, 56),
                               backgroundColor: _isGameRunning ? Colors.orange : Colors.green,
@@ -385,9 +396,14 @@ class _GamePageState extends State<GamePage> {
                               );
                               if (confirm == true) {
                                 try {
-                                  await uninstallGame(gameId: widget.game.id);
+                                  final warnings = await uninstallGame(gameId: widget.game.id);
                                   if (mounted) {
                                     _refreshGame();
+                                    if (warnings.isNotEmpty) {
+                                      ScaffoldMessenger.of(context).showSnackBar(
+                                        SnackBar(content: Text(warnings.join('\n'))),
+                                      );
+                                    }
                                   }
                                 } catch (e) {
                                   if (mounted) {
@@ -404,6 +420,22 @@ class _GamePageState extends State<GamePage> {
                               minimumSize: const Size(0, 56),
                             ),
                           ),
+                          const SizedBox(width: 16),
+                          IconButton.outlined(
+                            tooltip: 'Open install folder',
+                            onPressed: () async {
+                              try {
+                                await openInstallDir(widget.game.id);
+                              } catch (e) {
+                                if (mounted) {
+                                  ScaffoldMessenger.of(context).showSnackBar(
+                                    SnackBar(content: Text('Failed to open folder: $e')),
+                                  );
+                                }
+                              }
+                            },
+                            icon: const Icon(Icons.folder_open),
+                          ),
                         ],
                       ],
                     ),
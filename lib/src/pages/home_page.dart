@@ -31,12 +31,31 @@ class _HomePageState extends State<HomePage> {
   Future<void> _checkLoginStatus() async {
     setState(() => _isLoading = true);
     try {
-      // First check if we have a stored active account with refresh token
-      final activeAccount = await getActiveAccount();
-      if (activeAccount != null && activeAccount.refreshToken.isNotEmpty) {
-        // Try to authenticate using stored refresh token
+      // First try to restore the session of the stored active account
+      final AccountDto? activeAccount;
+      try {
+        activeAccount = await tryAutoLogin();
+      } catch (e) {
+        // Token revoked, the account has to log in again
+        setState(() {
+          _isLoggedIn = false;
+          _isLoading = false;
+        });
+        return;
+      }
+      if (activeAccount != null) {
         try {
-          await authenticate(refreshToken: activeAccount.refreshToken);
+          if (await isOffline()) {
+            // GOG is unreachable; show the stored library so installed games can be played
+            setState(() {
+              _isLoggedIn = true;
+              _username = activeAccount.username;
+              _avatarUrl = activeAccount.avatarUrl;
+              _accounts = [activeAccount];
+              _isLoading = false;
+            });
+            return;
+          }
           final userData = await getUserData();
           final accounts = await getAllAccounts();
           
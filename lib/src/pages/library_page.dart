@@ -1,3 +1,5 @@
+import 'dart:io';
+
 import 'package:flutter/material.dart';
 import 'package:galaxi/src/backend/api.dart';
 import 'package:galaxi/src/backend/dto.dart';
@@ -30,6 +32,7 @@ class LibraryPage extends StatefulWidget {
 class _LibraryPageState extends State<LibraryPage> {
   bool _isLoading = true;
   List<GameDto> _games = [];
+  Map<int, String> _thumbnails = {};
   String _searchQuery = '';
   bool _showInstalledOnly = false;
   bool _showWindowsGames = false;
@@ -40,6 +43,15 @@ class _LibraryPageState extends State<LibraryPage> {
     super.initState();
     _loadSettings();
     _loadLibrary();
+    _resumePendingDownloads();
+  }
+
+  Future<void> _resumePendingDownloads() async {
+    try {
+      await resumePendingDownloads();
+    } catch (e) {
+      // Downloads can still be resumed manually
+    }
   }
 
   Future<void> _loadSettings() async {
@@ -65,6 +77,12 @@ class _LibraryPageState extends State<LibraryPage> {
       // Get updated games from cache (includes updated install_dir values)
       final updatedGames = await getCachedGames();
       setState(() => _games = updatedGames);
+      _loadThumbnails();
+      if (await isOffline() && mounted) {
+        ScaffoldMessenger.of(context).showSnackBar(
+          const SnackBar(content: Text('Offline: showing your library from the last sync')),
+        );
+      }
     } catch (e) {
       if (mounted) {
         ScaffoldMessenger.of(context).showSnackBar(
@@ -75,6 +93,39 @@ class _LibraryPageState extends State<LibraryPage> {
     setState(() => _isLoading = false);
   }
 
+  Future<void> _loadThumbnails() async {
+    try {
+      final thumbnails = await ensureThumbnails();
+      if (mounted) {
+        setState(() => _thumbnails = thumbnails);
+      }
+    } catch (e) {
+      // Fall back to loading images from the network
+    }
+  }
+
+  /// Prefer the thumbnail cached by the backend over the network image
+  Widget _buildThumbnail(GameDto game, {double? width, double? height, required Widget placeholder, required Widget error}) {
+    final localPath = _thumbnails[game.id];
+    if (localPath != null) {
+      return Image.file(
+        File(localPath),
+        width: width,
+        height: height,
+        fit: BoxFit.cover,
+        errorBuilder: (context, e, stackTrace) => error,
+      );
+    }
+    return CachedNetworkImage(
+      imageUrl: 'https:${game.imageUrl}_196.jpg',
+      width: width,
+      height: height,
+      fit: BoxFit.cover,
+      placeholder: (context, url) => placeholder,
+      errorWidget: (context, url, e) => error,
+    );
+  }
+
   List<GameDto> get _filteredGames {
     var games = _games;
     
@@ -208,7 +259,13 @@ class _LibraryPageState extends State<LibraryPage> {
           widget.onAddAccount?.call();
         } else if (value.startsWith('switch:')) {
           final userId = value.substring(7);
-          await switchAccount(userId: userId);
+          final result = await switchAccount(userId: userId);
+          if (result.needsReauth && mounted) {
+            ScaffoldMessenger.of(context).showSnackBar(
+              const SnackBar(content: Text('Login expired, please sign in to this account again')),
+            );
+            widget.onAddAccount?.call();
+          }
           // Refresh the page
         }
       },
@@ -307,16 +364,15 @@ class _LibraryPageState extends State<LibraryPage> {
                   Container(color: Colors.grey[850] ?? Colors.grey[800]),
                   if (game.imageUrl.isNotEmpty)
                     Center(
-                      child: CachedNetworkImage(
-                        imageUrl: 'https:${game.imageUrl}_196.jpg',
-                        fit: BoxFit.cover, // Cover to fill the space
+                      child: _buildThumbnail(
+                        game,
                         width: double.infinity,
                         height: double.infinity,
-                        placeholder: (context, url) => Container(
+                        placeholder: Container(
                           color: Colors.grey[800],
                           child: const Center(child: CircularProgressIndicator(strokeWidth: 2)),
                         ),
-                        errorWidget: (context, url, error) => Container(
+                        error: Container(
                           color: Colors.grey[300],
                           child: const Icon(Icons.games, size: 48),
                         ),
@@ -375,17 +431,16 @@ class _LibraryPageState extends State<LibraryPage> {
         leading: ClipRRect(
           borderRadius: BorderRadius.circular(4),
           child: game.imageUrl.isNotEmpty
-              ? CachedNetworkImage(
-                  imageUrl: 'https:${game.imageUrl}_196.jpg',
+              ? _buildThumbnail(
+                  game,
                   width: 48,
                   height: 48,
-                  fit: BoxFit.cover,
-                  placeholder: (context, url) => Container(
+                  placeholder: Container(
                     width: 48,
                     height: 48,
                     color: Colors.grey[800],
                   ),
-                  errorWidget: (context, url, error) => Container(
+                  error: Container(
                     width: 48,
                     height: 48,
                     color: Colors.grey[300],
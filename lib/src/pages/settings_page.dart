@@ -22,6 +22,9 @@ class _SettingsPageState extends State<SettingsPage> {
   bool _darkTheme = false;
   bool _showWindowsGames = false;
   bool _keepInstallers = false;
+  bool _createApplicationsFile = false;
+  bool _installDirIncludeId = false;
+  bool _stayLoggedIn = true;
   String _winePrefix = '';
   String _wineExecutable = '';
   bool _wineDebug = false;
@@ -41,6 +44,9 @@ class _SettingsPageState extends State<SettingsPage> {
       final darkTheme = await getDarkTheme();
       final showWindows = await getShowWindowsGames();
       final keepInstallers = await getKeepInstallers();
+      final createApplicationsFile = await getCreateApplicationsFile();
+      final installDirIncludeId = await getInstallDirIncludeId();
+      final stayLoggedIn = await getStayLoggedIn();
       final winePrefix = await getWinePrefix();
       final wineExecutable = await getWineExecutable();
       final wineDebug = await getWineDebug();
@@ -52,6 +58,9 @@ class _SettingsPageState extends State<SettingsPage> {
         _darkTheme = darkTheme;
         _showWindowsGames = showWindows;
         _keepInstallers = keepInstallers;
+        _createApplicationsFile = createApplicationsFile;
+        _installDirIncludeId = installDirIncludeId;
+        _stayLoggedIn = stayLoggedIn;
         _winePrefix = winePrefix;
         _wineExecutable = wineExecutable;
         _wineDebug = wineDebug;
@@ -132,6 +141,36 @@ class _SettingsPageState extends State<SettingsPage> {
               setState(() => _keepInstallers = value);
             },
           ),
+          SwitchListTile(
+            secondary: const Icon(Icons.apps),
+            title: const Text('Create Menu Shortcuts'),
+            subtitle: const Text('Add installed games to the application menu'),
+            value: _createApplicationsFile,
+            onChanged: (value) async {
+              await setCreateApplicationsFile(enabled: value);
+              setState(() => _createApplicationsFile = value);
+            },
+          ),
+          SwitchListTile(
+            secondary: const Icon(Icons.tag),
+            title: const Text('Add Game ID to Folder Names'),
+            subtitle: const Text('Keeps games with similar titles apart; applies to new installs'),
+            value: _installDirIncludeId,
+            onChanged: (value) async {
+              await setInstallDirIncludeId(enabled: value);
+              setState(() => _installDirIncludeId = value);
+            },
+          ),
+          SwitchListTile(
+            secondary: const Icon(Icons.login),
+            title: const Text('Stay Logged In'),
+            subtitle: const Text('Remember the login between starts'),
+            value: _stayLoggedIn,
+            onChanged: (value) async {
+              await setStayLoggedIn(enabled: value);
+              setState(() => _stayLoggedIn = value);
+            },
+          ),
           const Divider(),
           const Padding(
             padding: EdgeInsets.all(16.0),
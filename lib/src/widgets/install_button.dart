@@ -68,7 +68,7 @@ class _InstallButtonState extends State<InstallButton> {
             if (progress.status == 'Completed') {
               downloadComplete = true;
             } else if (progress.status == 'Failed') {
-              throw Exception('Download failed');
+              throw Exception(progress.errorMessage ?? 'Download failed');
             } else if (progress.status == 'Cancelled') {
               throw Exception('Download cancelled');
             }
@@ -0,0 +1,1035 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import * as zlib from 'zlib';
+import * as crypto from 'crypto';
+import { Readable } from 'stream';
+import { pipeline } from 'stream/promises';
+import { GalaxiError, GalaxiErrorType } from './error';
+
+// Archive formats GameInstaller can unpack, matched on the file name
+const ARCHIVE_SUFFIXES = ['.zip', '.tar.gz', '.tgz', '.tar.xz'];
+
+export function isArchive(fileName: string): boolean {
+  const lower = fileName.toLowerCase();
+  return ARCHIVE_SUFFIXES.some(suffix => lower.endsWith(suffix));
+}
+
+/**
+ * Extract a .zip, .tar.gz or .tar.xz archive into a directory, keeping
+ * executable bits. Entries that would land outside the directory, directly
+ * or through a symlink, are rejected.
+ */
+export async function extractArchive(archivePath: string, destDir: string): Promise<void> {
+  const lower = archivePath.toLowerCase();
+  fs.mkdirSync(destDir, { recursive: true });
+  try {
+    if (lower.endsWith('.zip')) {
+      await extractZip(archivePath, destDir);
+    } else if (lower.endsWith('.tar.gz') || lower.endsWith('.tgz')) {
+      await extractTar(fs.createReadStream(archivePath).pipe(zlib.createGunzip()), destDir);
+    } else if (lower.endsWith('.tar.xz')) {
+      await extractTar(Readable.from(decompressXz(archivePath)), destDir);
+    } else {
+      throw new GalaxiError(`Unsupported archive type: ${path.basename(archivePath)}`, GalaxiErrorType.InstallError);
+    }
+  } catch (error: any) {
+    if (error instanceof GalaxiError) {
+      throw error;
+    }
+    throw new GalaxiError(
+      `Failed to extract ${path.basename(archivePath)}: ${error.message}`,
+      GalaxiErrorType.InstallError
+    );
+  }
+}
+
+function corrupt(reason: string): GalaxiError {
+  return new GalaxiError(`Corrupt archive: ${reason}`, GalaxiErrorType.InstallError);
+}
+
+/**
+ * Resolve an entry name inside the destination, refusing absolute paths and `..`
+ */
+function entryPath(destDir: string, name: string): string {
+  const target = path.resolve(destDir, name);
+  const root = path.resolve(destDir);
+  if (target !== root && !target.startsWith(root + path.sep)) {
+    throw corrupt(`entry escapes the install directory: ${name}`);
+  }
+  return target;
+}
+
+/**
+ * Resolve an entry that is about to be written, additionally refusing one
+ * whose parent directories include a symlink: an earlier entry could have
+ * made `lnk -> /home/user` so that `lnk/.bashrc` writes outside.
+ */
+function writableEntryPath(destDir: string, name: string): string {
+  const target = entryPath(destDir, name);
+  let current = path.resolve(destDir);
+  for (const part of path.relative(current, path.dirname(target)).split(path.sep).filter(p => p.length > 0)) {
+    current = path.join(current, part);
+    let stat: fs.Stats;
+    try {
+      stat = fs.lstatSync(current);
+    } catch {
+      // The rest doesn't exist yet and is created as real directories
+      break;
+    }
+    if (stat.isSymbolicLink()) {
+      throw corrupt(`entry is written through a symlink: ${name}`);
+    }
+  }
+  return target;
+}
+
+/**
+ * Create a symlink entry, refusing one that points outside the destination
+ */
+function createSymlink(destDir: string, target: string, linkTarget: string): void {
+  const resolved = path.resolve(path.dirname(target), linkTarget);
+  const root = path.resolve(destDir);
+  if (resolved !== root && !resolved.startsWith(root + path.sep)) {
+    throw corrupt(`symlink points outside the install directory: ${linkTarget}`);
+  }
+  fs.symlinkSync(linkTarget, target);
+}
+
+function applyMode(filePath: string, mode: number): void {
+  if (mode & 0o111) {
+    fs.chmodSync(filePath, mode & 0o777);
+  }
+}
+
+// ============================================================================
+// ZIP
+// ============================================================================
+
+const ZIP_EOCD_SIGNATURE = 0x06054b50;
+const ZIP_CENTRAL_SIGNATURE = 0x02014b50;
+const ZIP_LOCAL_SIGNATURE = 0x04034b50;
+// End of central directory record plus the longest possible comment
+const ZIP_EOCD_MAX_SEARCH = 22 + 0xffff;
+const ZIP_HOST_UNIX = 3;
+const S_IFMT = 0o170000;
+const S_IFLNK = 0o120000;
+
+function readAt(fd: number, position: number, length: number): Buffer {
+  const buffer = Buffer.alloc(length);
+  const read = fs.readSync(fd, buffer, 0, length, position);
+  if (read !== length) {
+    throw corrupt('unexpected end of file');
+  }
+  return buffer;
+}
+
+async function extractZip(archivePath: string, destDir: string): Promise<void> {
+  await extractZipEntries(archivePath, destDir, name => name);
+}
+
+/**
+ * Extract the zip entries `select` maps to a path relative to `destDir`,
+ * skipping those it maps to null. Returns the paths written. The zip may sit
+ * behind a stub, as in GOG's makeself .sh installers.
+ */
+export async function extractZipEntries(
+  archivePath: string,
+  destDir: string,
+  select: (name: string) => string | null
+): Promise<string[]> {
+  const written: string[] = [];
+  const fd = fs.openSync(archivePath, 'r');
+  try {
+    const fileSize = fs.fstatSync(fd).size;
+    const tailLength = Math.min(fileSize, ZIP_EOCD_MAX_SEARCH);
+    const tail = readAt(fd, fileSize - tailLength, tailLength);
+
+    let eocd = -1;
+    for (let i = tail.length - 22; i >= 0; i--) {
+      if (tail.readUInt32LE(i) === ZIP_EOCD_SIGNATURE) {
+        eocd = i;
+        break;
+      }
+    }
+    if (eocd < 0) {
+      throw corrupt('no end of central directory record');
+    }
+
+    const entryCount = tail.readUInt16LE(eocd + 10);
+    const directorySize = tail.readUInt32LE(eocd + 12);
+    const directoryOffset = tail.readUInt32LE(eocd + 16);
+    if (entryCount === 0xffff || directoryOffset === 0xffffffff) {
+      throw new GalaxiError('ZIP64 archives are not supported', GalaxiErrorType.InstallError);
+    }
+
+    // Offsets are relative to the start of the zip, which follows the stub
+    const base = fileSize - tailLength + eocd - directorySize - directoryOffset;
+    if (base < 0) {
+      throw corrupt('central directory out of bounds');
+    }
+
+    const directory = readAt(fd, base + directoryOffset, directorySize);
+    let offset = 0;
+    for (let i = 0; i < entryCount; i++) {
+      if (offset + 46 > directory.length || directory.readUInt32LE(offset) !== ZIP_CENTRAL_SIGNATURE) {
+        throw corrupt('bad central directory entry');
+      }
+      const host = directory.readUInt8(offset + 5);
+      const method = directory.readUInt16LE(offset + 10);
+      const compressedSize = directory.readUInt32LE(offset + 20);
+      const size = directory.readUInt32LE(offset + 24);
+      const nameLength = directory.readUInt16LE(offset + 28);
+      const extraLength = directory.readUInt16LE(offset + 30);
+      const commentLength = directory.readUInt16LE(offset + 32);
+      const externalAttributes = directory.readUInt32LE(offset + 38);
+      const localOffset = directory.readUInt32LE(offset + 42);
+      const name = directory.toString('utf-8', offset + 46, offset + 46 + nameLength);
+      offset += 46 + nameLength + extraLength + commentLength;
+
+      const mode = host === ZIP_HOST_UNIX ? externalAttributes >>> 16 : 0;
+      const selected = select(name);
+      if (selected === null) {
+        continue;
+      }
+      const target = writableEntryPath(destDir, selected);
+      if (name.endsWith('/')) {
+        fs.mkdirSync(target, { recursive: true });
+        continue;
+      }
+
+      const local = readAt(fd, base + localOffset, 30);
+      if (local.readUInt32LE(0) !== ZIP_LOCAL_SIGNATURE) {
+        throw corrupt(`bad local header for ${name}`);
+      }
+      const dataStart = base + localOffset + 30 + local.readUInt16LE(26) + local.readUInt16LE(28);
+
+      fs.mkdirSync(path.dirname(target), { recursive: true });
+      // Never write through a symlink left by an earlier entry
+      fs.rmSync(target, { force: true });
+      if ((mode & S_IFMT) === S_IFLNK) {
+        createSymlink(destDir, target, readAt(fd, dataStart, compressedSize).toString('utf-8'));
+        written.push(selected);
+        continue;
+      }
+
+      const raw = compressedSize > 0
+        ? fs.createReadStream(archivePath, { start: dataStart, end: dataStart + compressedSize - 1 })
+        : Readable.from([]);
+      if (method === 8) {
+        await pipeline(raw, zlib.createInflateRaw(), fs.createWriteStream(target));
+      } else if (method === 0) {
+        await pipeline(raw, fs.createWriteStream(target));
+      } else {
+        throw new GalaxiError(`Unsupported compression method ${method} for ${name}`, GalaxiErrorType.InstallError);
+      }
+
+      if (fs.statSync(target).size !== size) {
+        throw corrupt(`size mismatch for ${name}`);
+      }
+      applyMode(target, mode);
+      written.push(selected);
+    }
+  } finally {
+    fs.closeSync(fd);
+  }
+  return written;
+}
+
+// ============================================================================
+// TAR
+// ============================================================================
+
+const TAR_BLOCK = 512;
+
+function tarString(header: Buffer, start: number, length: number): string {
+  const field = header.subarray(start, start + length);
+  const end = field.indexOf(0);
+  return field.toString('utf-8', 0, end < 0 ? length : end);
+}
+
+function tarNumber(header: Buffer, start: number, length: number): number {
+  // GNU base-256 encoding for values that don't fit in octal
+  if (header[start] & 0x80) {
+    let value = 0;
+    for (let i = start + 1; i < start + length; i++) {
+      value = value * 256 + header[i];
+    }
+    return value;
+  }
+  const text = tarString(header, start, length).trim();
+  if (text.length > 0 && !/^[0-7]+$/.test(text)) {
+    throw corrupt('invalid tar header');
+  }
+  return text.length > 0 ? parseInt(text, 8) : 0;
+}
+
+function tarChecksumMatches(header: Buffer): boolean {
+  let sum = 0;
+  for (let i = 0; i < TAR_BLOCK; i++) {
+    // The checksum field itself counts as spaces
+    sum += i >= 148 && i < 156 ? 0x20 : header[i];
+  }
+  return sum === tarNumber(header, 148, 8);
+}
+
+/**
+ * Path from a pax extended header, if it sets one
+ */
+function paxPath(data: Buffer): string | undefined {
+  let offset = 0;
+  let result: string | undefined;
+  while (offset < data.length) {
+    const space = data.indexOf(0x20, offset);
+    if (space < 0) {
+      break;
+    }
+    const length = parseInt(data.toString('utf-8', offset, space), 10);
+    if (!length) {
+      break;
+    }
+    const record = data.toString('utf-8', space + 1, offset + length - 1);
+    const separator = record.indexOf('=');
+    if (record.slice(0, separator) === 'path') {
+      result = record.slice(separator + 1);
+    }
+    offset += length;
+  }
+  return result;
+}
+
+/**
+ * Unpack a tar stream. Entries are written as they arrive, so memory use
+ * doesn't depend on the archive size.
+ */
+async function extractTar(stream: Readable, destDir: string): Promise<void> {
+  let pending = Buffer.alloc(0);
+  let ended = false;
+
+  // Current entry being written, or metadata being collected
+  let entry: { remaining: number; padding: number; fd?: number; data?: Buffer[]; kind: string; target?: string; mode: number } | null = null;
+  let longName: string | undefined;
+
+  const finishEntry = () => {
+    if (!entry) {
+      return;
+    }
+    if (entry.fd !== undefined) {
+      fs.closeSync(entry.fd);
+      applyMode(entry.target!, entry.mode);
+    } else if (entry.data) {
+      const data = Buffer.concat(entry.data);
+      if (entry.kind === 'L') {
+        longName = tarString(data, 0, data.length);
+      } else if (entry.kind === 'x') {
+        longName = paxPath(data) ?? longName;
+      }
+    }
+    entry = null;
+  };
+
+  for await (const chunk of stream) {
+    pending = pending.length > 0 ? Buffer.concat([pending, chunk as Buffer]) : (chunk as Buffer);
+
+    while (!ended) {
+      if (entry) {
+        if (entry.remaining > 0) {
+          if (pending.length === 0) {
+            break;
+          }
+          const take = Math.min(entry.remaining, pending.length);
+          const piece = pending.subarray(0, take);
+          if (entry.fd !== undefined) {
+            fs.writeSync(entry.fd, piece);
+          } else if (entry.data) {
+            entry.data.push(Buffer.from(piece));
+          }
+          entry.remaining -= take;
+          pending = pending.subarray(take);
+          continue;
+        }
+        if (pending.length < entry.padding) {
+          break;
+        }
+        pending = pending.subarray(entry.padding);
+        finishEntry();
+        continue;
+      }
+
+      if (pending.length < TAR_BLOCK) {
+        break;
+      }
+      const header = pending.subarray(0, TAR_BLOCK);
+      pending = pending.subarray(TAR_BLOCK);
+      if (header.every(byte => byte === 0)) {
+        ended = true;
+        break;
+      }
+      if (!tarChecksumMatches(header)) {
+        throw corrupt('tar header checksum mismatch');
+      }
+
+      const kind = String.fromCharCode(header[156] || 0x30);
+      const size = tarNumber(header, 124, 12);
+      const mode = tarNumber(header, 100, 8);
+      const prefix = tarString(header, 345, 155);
+      const baseName = tarString(header, 0, 100);
+      const name = longName ?? (prefix ? `${prefix}/${baseName}` : baseName);
+      const padding = (TAR_BLOCK - (size % TAR_BLOCK)) % TAR_BLOCK;
+
+      if (kind === 'L' || kind === 'x') {
+        entry = { remaining: size, padding, data: [], kind, mode };
+        continue;
+      }
+      longName = undefined;
+      // Global pax headers and anything we don't create (devices, fifos) are skipped
+      if (kind !== '0' && kind !== '7' && kind !== '5' && kind !== '2' && kind !== '1') {
+        entry = { remaining: size, padding, kind, mode };
+        continue;
+      }
+
+      const target = writableEntryPath(destDir, name);
+      if (kind === '5') {
+        fs.mkdirSync(target, { recursive: true });
+        entry = { remaining: 0, padding: 0, kind, mode };
+        continue;
+      }
+
+      fs.mkdirSync(path.dirname(target), { recursive: true });
+      fs.rmSync(target, { force: true });
+      if (kind === '2') {
+        createSymlink(destDir, target, tarString(header, 157, 100));
+        entry = { remaining: size, padding, kind, mode };
+      } else if (kind === '1') {
+        // Hard links are copied; the source must be a regular file extracted earlier
+        const source = writableEntryPath(destDir, tarString(header, 157, 100));
+        if (!fs.lstatSync(source, { throwIfNoEntry: false })?.isFile()) {
+          throw corrupt(`hard link to a missing or special file: ${name}`);
+        }
+        fs.copyFileSync(source, target);
+        entry = { remaining: size, padding, kind, mode };
+      } else {
+        entry = { remaining: size, padding, fd: fs.openSync(target, 'w'), target, kind, mode };
+      }
+    }
+  }
+
+  if (entry && (entry.remaining > 0 || entry.fd !== undefined)) {
+    if (entry.fd !== undefined) {
+      fs.closeSync(entry.fd);
+    }
+    throw corrupt('unexpected end of tar data');
+  }
+  if (!ended && pending.length > 0) {
+    throw corrupt('truncated tar header');
+  }
+}
+
+
+// ============================================================================
+// XZ
+// ============================================================================
+
+// Neither Node nor Bun ships an xz decoder, so .tar.xz is decompressed here.
+// Only the LZMA2 filter is supported, which is what xz uses unless told
+// otherwise.
+
+const XZ_HEADER_MAGIC = Buffer.from([0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00]);
+const XZ_FOOTER_MAGIC = Buffer.from([0x59, 0x5a]);
+const XZ_FILTER_LZMA2 = 0x21;
+// Size of the block check for each check type
+const XZ_CHECK_SIZES = [0, 4, 4, 4, 8, 8, 8, 16, 16, 16, 32, 32, 32, 64, 64, 64];
+const XZ_CHECK_CRC32 = 1;
+const XZ_CHECK_CRC64 = 4;
+const XZ_CHECK_SHA256 = 10;
+const XZ_READ_SIZE = 1024 * 1024;
+
+const CRC32_TABLE = new Uint32Array(256);
+// CRC-64/XZ split in 32-bit halves, as JS bit operations are 32-bit
+const CRC64_TABLE_LO = new Uint32Array(256);
+const CRC64_TABLE_HI = new Uint32Array(256);
+for (let n = 0; n < 256; n++) {
+  let c = n;
+  let lo = n;
+  let hi = 0;
+  for (let k = 0; k < 8; k++) {
+    c = c & 1 ? 0xedb88320 ^ (c >>> 1) : c >>> 1;
+    const carry = lo & 1;
+    lo = ((lo >>> 1) | (hi << 31)) >>> 0;
+    hi = hi >>> 1;
+    if (carry) {
+      lo = (lo ^ 0xd7870f42) >>> 0;
+      hi = (hi ^ 0xc96c5795) >>> 0;
+    }
+  }
+  CRC32_TABLE[n] = c >>> 0;
+  CRC64_TABLE_LO[n] = lo;
+  CRC64_TABLE_HI[n] = hi;
+}
+
+function crc32(data: Buffer, crc: number = 0): number {
+  let c = ~crc >>> 0;
+  for (let i = 0; i < data.length; i++) {
+    c = CRC32_TABLE[(c ^ data[i]) & 0xff] ^ (c >>> 8);
+  }
+  return ~c >>> 0;
+}
+
+/**
+ * Running block check of one xz block
+ */
+class XzCheck {
+  private type: number;
+  private crc32 = 0;
+  private crc64Lo = 0xffffffff;
+  private crc64Hi = 0xffffffff;
+  private sha256?: crypto.Hash;
+
+  constructor(type: number) {
+    this.type = type;
+    if (type === XZ_CHECK_SHA256) {
+      this.sha256 = crypto.createHash('sha256');
+    }
+  }
+
+  update(data: Buffer): void {
+    if (this.type === XZ_CHECK_CRC32) {
+      this.crc32 = crc32(data, this.crc32);
+    } else if (this.type === XZ_CHECK_CRC64) {
+      let lo = this.crc64Lo;
+      let hi = this.crc64Hi;
+      for (let i = 0; i < data.length; i++) {
+        const index = (lo ^ data[i]) & 0xff;
+        lo = (((lo >>> 8) | (hi << 24)) ^ CRC64_TABLE_LO[index]) >>> 0;
+        hi = ((hi >>> 8) ^ CRC64_TABLE_HI[index]) >>> 0;
+      }
+      this.crc64Lo = lo;
+      this.crc64Hi = hi;
+    } else if (this.sha256) {
+      this.sha256.update(data);
+    }
+  }
+
+  /**
+   * Whether the stored check matches; check types we can't compute pass
+   */
+  matches(stored: Buffer): boolean {
+    if (this.type === XZ_CHECK_CRC32) {
+      return stored.readUInt32LE(0) === this.crc32;
+    }
+    if (this.type === XZ_CHECK_CRC64) {
+      return stored.readUInt32LE(0) === (~this.crc64Lo >>> 0) && stored.readUInt32LE(4) === (~this.crc64Hi >>> 0);
+    }
+    if (this.sha256) {
+      return stored.equals(this.sha256.digest());
+    }
+    return true;
+  }
+}
+
+/**
+ * Buffered sequential reads from a file, with the current offset
+ */
+class ByteReader {
+  private fd: number;
+  private buffer = Buffer.alloc(XZ_READ_SIZE);
+  private bufferStart = 0;
+  private bufferLength = 0;
+  position = 0;
+  readonly size: number;
+
+  constructor(fd: number) {
+    this.fd = fd;
+    this.size = fs.fstatSync(fd).size;
+  }
+
+  byte(): number {
+    const index = this.position - this.bufferStart;
+    if (index < 0 || index >= this.bufferLength) {
+      this.bufferStart = this.position;
+      this.bufferLength = fs.readSync(this.fd, this.buffer, 0, this.buffer.length, this.position);
+      if (this.bufferLength === 0) {
+        throw corrupt('unexpected end of file');
+      }
+      this.position++;
+      return this.buffer[0];
+    }
+    this.position++;
+    return this.buffer[index];
+  }
+
+  bytes(length: number): Buffer {
+    const result = Buffer.alloc(length);
+    for (let i = 0; i < length; i++) {
+      result[i] = this.byte();
+    }
+    return result;
+  }
+
+  /**
+   * xz variable-length integer
+   */
+  varint(): number {
+    let value = 0;
+    for (let i = 0; i < 9; i++) {
+      const byte = this.byte();
+      value += (byte & 0x7f) * 2 ** (7 * i);
+      if (!(byte & 0x80)) {
+        return value;
+      }
+    }
+    throw corrupt('invalid xz integer');
+  }
+}
+
+const LZMA_STATES = 12;
+const LZMA_POS_STATES_MAX = 16;
+const LZMA_END_POS_MODEL_INDEX = 14;
+const LZMA_FULL_DISTANCES = 128;
+const LZMA_MATCH_MIN_LEN = 2;
+
+/**
+ * Length decoder probabilities: choice, choice 2, then the low, mid and
+ * high bit trees
+ */
+function newLengthProbs(): Uint16Array {
+  return new Uint16Array(2 + LZMA_POS_STATES_MAX * 8 * 2 + 256);
+}
+
+/**
+ * LZMA2 decoder with its dictionary. Every LZMA2 chunk decodes to at most
+ * 2 MiB, which is handed out as one buffer.
+ */
+class Lzma2Decoder {
+  private input: ByteReader;
+  private dict: Uint8Array;
+  private dictPos = 0;
+  private dictFull = 0;
+  // Bytes since the last dictionary reset, for the position-dependent contexts
+  private totalPos = 0;
+  private needDictReset = true;
+  private needProps = true;
+
+  private lc = 0;
+  private lp = 0;
+  private pb = 0;
+  private state = 0;
+  private reps = [0, 0, 0, 0];
+  private isMatch = new Uint16Array(LZMA_STATES * LZMA_POS_STATES_MAX);
+  private isRep = new Uint16Array(LZMA_STATES);
+  private isRepG0 = new Uint16Array(LZMA_STATES);
+  private isRepG1 = new Uint16Array(LZMA_STATES);
+  private isRepG2 = new Uint16Array(LZMA_STATES);
+  private isRep0Long = new Uint16Array(LZMA_STATES * LZMA_POS_STATES_MAX);
+  private posSlot = new Uint16Array(4 * 64);
+  private posSpecial = new Uint16Array(1 + LZMA_FULL_DISTANCES - LZMA_END_POS_MODEL_INDEX);
+  private align = new Uint16Array(16);
+  private matchLength = newLengthProbs();
+  private repLength = newLengthProbs();
+  private literal = new Uint16Array(0x300);
+
+  private range = 0;
+  private code = 0;
+
+  constructor(input: ByteReader, dictSize: number) {
+    this.input = input;
+    this.dict = new Uint8Array(dictSize);
+  }
+
+  /**
+   * Decode the next chunk, or return null at the end of the LZMA2 data
+   */
+  nextChunk(): Buffer | null {
+    const control = this.input.byte();
+    if (control === 0x00) {
+      return null;
+    }
+    if (control === 0x01 || control === 0x02) {
+      if (control === 0x01) {
+        this.resetDict();
+      } else if (this.needDictReset) {
+        throw corrupt('missing xz dictionary reset');
+      }
+      const size = ((this.input.byte() << 8) | this.input.byte()) + 1;
+      const out = this.input.bytes(size);
+      for (let i = 0; i < size; i++) {
+        this.putByte(out[i]);
+      }
+      return out;
+    }
+    if (control < 0x80) {
+      throw corrupt('invalid LZMA2 chunk');
+    }
+
+    const unpackedSize = ((control & 0x1f) << 16) + ((this.input.byte() << 8) | this.input.byte()) + 1;
+    const packedSize = ((this.input.byte() << 8) | this.input.byte()) + 1;
+    const reset = (control >> 5) & 3;
+    if (reset === 3) {
+      this.resetDict();
+    } else if (this.needDictReset) {
+      throw corrupt('missing xz dictionary reset');
+    }
+    if (reset >= 2) {
+      this.setProps(this.input.byte());
+    } else if (this.needProps) {
+      throw corrupt('missing LZMA properties');
+    }
+    if (reset >= 1) {
+      this.resetState();
+    }
+
+    const start = this.input.position;
+    const out = Buffer.alloc(unpackedSize);
+    this.initRangeDecoder();
+    this.decodeLzma(out);
+    if (this.input.position - start > packedSize) {
+      throw corrupt('LZMA2 chunk overruns its packed size');
+    }
+    this.input.position = start + packedSize;
+    return out;
+  }
+
+  private resetDict(): void {
+    this.dictPos = 0;
+    this.dictFull = 0;
+    this.totalPos = 0;
+    this.needDictReset = false;
+  }
+
+  private setProps(props: number): void {
+    if (props > (4 * 5 + 4) * 9 + 8) {
+      throw corrupt('invalid LZMA properties');
+    }
+    this.lc = props % 9;
+    props = Math.floor(props / 9);
+    this.lp = props % 5;
+    this.pb = Math.floor(props / 5);
+    if (this.lc + this.lp > 4) {
+      throw corrupt('invalid LZMA2 properties');
+    }
+    this.literal = new Uint16Array(0x300 << (this.lc + this.lp));
+    this.needProps = false;
+  }
+
+  private resetState(): void {
+    this.state = 0;
+    this.reps.fill(0);
+    for (const probs of [
+      this.isMatch, this.isRep, this.isRepG0, this.isRepG1, this.isRepG2, this.isRep0Long,
+      this.posSlot, this.posSpecial, this.align, this.matchLength, this.repLength, this.literal,
+    ]) {
+      probs.fill(1024);
+    }
+  }
+
+  private initRangeDecoder(): void {
+    if (this.input.byte() !== 0) {
+      throw corrupt('invalid LZMA range coder data');
+    }
+    this.range = 0xffffffff;
+    this.code = 0;
+    for (let i = 0; i < 4; i++) {
+      this.code = ((this.code << 8) | this.input.byte()) >>> 0;
+    }
+  }
+
+  private bit(probs: Uint16Array, index: number): number {
+    const prob = probs[index];
+    const bound = (this.range >>> 11) * prob;
+    let bit: number;
+    if (this.code < bound) {
+      this.range = bound;
+      probs[index] = prob + ((2048 - prob) >>> 5);
+      bit = 0;
+    } else {
+      this.range -= bound;
+      this.code -= bound;
+      probs[index] = prob - (prob >>> 5);
+      bit = 1;
+    }
+    if (this.range < 0x1000000) {
+      this.range = (this.range << 8) >>> 0;
+      this.code = ((this.code << 8) | this.input.byte()) >>> 0;
+    }
+    return bit;
+  }
+
+  private directBits(count: number): number {
+    let result = 0;
+    for (let i = 0; i < count; i++) {
+      this.range >>>= 1;
+      let bit = 0;
+      if (this.code >= this.range) {
+        this.code -= this.range;
+        bit = 1;
+      }
+      result = ((result << 1) | bit) >>> 0;
+      if (this.range < 0x1000000) {
+        this.range = (this.range << 8) >>> 0;
+        this.code = ((this.code << 8) | this.input.byte()) >>> 0;
+      }
+    }
+    return result;
+  }
+
+  private bitTree(probs: Uint16Array, offset: number, bits: number): number {
+    let m = 1;
+    for (let i = 0; i < bits; i++) {
+      m = (m << 1) | this.bit(probs, offset + m);
+    }
+    return m - (1 << bits);
+  }
+
+  private reverseBitTree(probs: Uint16Array, offset: number, bits: number): number {
+    let m = 1;
+    let symbol = 0;
+    for (let i = 0; i < bits; i++) {
+      const bit = this.bit(probs, offset + m);
+      m = (m << 1) | bit;
+      symbol |= bit << i;
+    }
+    return symbol;
+  }
+
+  private length(probs: Uint16Array, posState: number): number {
+    if (this.bit(probs, 0) === 0) {
+      return this.bitTree(probs, 2 + posState * 8, 3);
+    }
+    if (this.bit(probs, 1) === 0) {
+      return 8 + this.bitTree(probs, 2 + LZMA_POS_STATES_MAX * 8 + posState * 8, 3);
+    }
+    return 16 + this.bitTree(probs, 2 + LZMA_POS_STATES_MAX * 8 * 2, 8);
+  }
+
+  private distance(length: number): number {
+    const slot = this.bitTree(this.posSlot, Math.min(length, 3) * 64, 6);
+    if (slot < 4) {
+      return slot;
+    }
+    const directBits = (slot >>> 1) - 1;
+    let distance = ((2 | (slot & 1)) << directBits) >>> 0;
+    if (slot < LZMA_END_POS_MODEL_INDEX) {
+      return distance + this.reverseBitTree(this.posSpecial, distance - slot, directBits);
+    }
+    distance += this.directBits(directBits - 4) * 16;
+    return distance + this.reverseBitTree(this.align, 0, 4);
+  }
+
+  private putByte(byte: number): void {
+    this.dict[this.dictPos++] = byte;
+    if (this.dictPos === this.dict.length) {
+      this.dictPos = 0;
+    }
+    if (this.dictFull < this.dict.length) {
+      this.dictFull++;
+    }
+    this.totalPos++;
+  }
+
+  private dictByte(distance: number): number {
+    let index = this.dictPos - distance - 1;
+    if (index < 0) {
+      index += this.dict.length;
+    }
+    return this.dict[index];
+  }
+
+  private decodeLzma(out: Buffer): void {
+    const pbMask = (1 << this.pb) - 1;
+    const lpMask = (1 << this.lp) - 1;
+    let written = 0;
+    while (written < out.length) {
+      const posState = this.totalPos & pbMask;
+      if (this.bit(this.isMatch, (this.state << 4) + posState) === 0) {
+        const previous = this.dictFull > 0 ? this.dictByte(0) : 0;
+        const offset = 0x300 * (((this.totalPos & lpMask) << this.lc) + (previous >>> (8 - this.lc)));
+        let symbol = 1;
+        if (this.state >= 7) {
+          let matchByte = this.dictByte(this.reps[0]);
+          while (symbol < 0x100) {
+            const matchBit = (matchByte >>> 7) & 1;
+            matchByte <<= 1;
+            const bit = this.bit(this.literal, offset + ((1 + matchBit) << 8) + symbol);
+            symbol = (symbol << 1) | bit;
+            if (matchBit !== bit) {
+              break;
+            }
+          }
+        }
+        while (symbol < 0x100) {
+          symbol = (symbol << 1) | this.bit(this.literal, offset + symbol);
+        }
+        this.putByte(symbol & 0xff);
+        out[written++] = symbol & 0xff;
+        this.state = this.state < 4 ? 0 : this.state < 10 ? this.state - 3 : this.state - 6;
+        continue;
+      }
+
+      let length: number;
+      if (this.bit(this.isRep, this.state) === 0) {
+        length = this.length(this.matchLength, posState);
+        this.state = this.state < 7 ? 7 : 10;
+        this.reps[3] = this.reps[2];
+        this.reps[2] = this.reps[1];
+        this.reps[1] = this.reps[0];
+        this.reps[0] = this.distance(length);
+        if (this.reps[0] === 0xffffffff) {
+          throw corrupt('unexpected LZMA end marker');
+        }
+      } else {
+        if (this.bit(this.isRepG0, this.state) === 0) {
+          if (this.bit(this.isRep0Long, (this.state << 4) + posState) === 0) {
+            if (this.dictFull === 0) {
+              throw corrupt('LZMA repeat before any data');
+            }
+            this.state = this.state < 7 ? 9 : 11;
+            const byte = this.dictByte(this.reps[0]);
+            this.putByte(byte);
+            out[written++] = byte;
+            continue;
+          }
+        } else {
+          let distance: number;
+          if (this.bit(this.isRepG1, this.state) === 0) {
+            distance = this.reps[1];
+          } else {
+            if (this.bit(this.isRepG2, this.state) === 0) {
+              distance = this.reps[2];
+            } else {
+              distance = this.reps[3];
+              this.reps[3] = this.reps[2];
+            }
+            this.reps[2] = this.reps[1];
+          }
+          this.reps[1] = this.reps[0];
+          this.reps[0] = distance;
+        }
+        length = this.length(this.repLength, posState);
+        this.state = this.state < 7 ? 8 : 11;
+      }
+
+      length += LZMA_MATCH_MIN_LEN;
+      if (this.reps[0] >= this.dictFull) {
+        throw corrupt('LZMA match distance out of range');
+      }
+      if (written + length > out.length) {
+        throw corrupt('LZMA match overruns its chunk');
+      }
+      for (let i = 0; i < length; i++) {
+        const byte = this.dictByte(this.reps[0]);
+        this.putByte(byte);
+        out[written++] = byte;
+      }
+    }
+  }
+}
+
+/**
+ * Read a block header and return the LZMA2 dictionary size it declares
+ */
+function readXzBlockHeader(input: ByteReader, firstByte: number): number {
+  const header = Buffer.concat([Buffer.from([firstByte]), input.bytes((firstByte + 1) * 4 - 1)]);
+  if (crc32(header.subarray(0, header.length - 4)) !== header.readUInt32LE(header.length - 4)) {
+    throw corrupt('xz block header checksum mismatch');
+  }
+  const flags = header[1];
+  if (flags & 0x3c) {
+    throw corrupt('unsupported xz block flags');
+  }
+  // Parse the rest of the header from memory with a reader-like cursor
+  let offset = 2;
+  const varint = (): number => {
+    let value = 0;
+    for (let i = 0; i < 9 && offset < header.length; i++) {
+      const byte = header[offset++];
+      value += (byte & 0x7f) * 2 ** (7 * i);
+      if (!(byte & 0x80)) {
+        return value;
+      }
+    }
+    throw corrupt('invalid xz block header');
+  };
+  if (flags & 0x40) {
+    varint();
+  }
+  if (flags & 0x80) {
+    varint();
+  }
+  if ((flags & 3) !== 0 || varint() !== XZ_FILTER_LZMA2 || varint() !== 1) {
+    throw new GalaxiError('Unsupported xz filter; only plain LZMA2 archives can be extracted', GalaxiErrorType.InstallError);
+  }
+  const dictProps = header[offset] & 0x3f;
+  if (dictProps > 40) {
+    throw corrupt('invalid LZMA2 dictionary size');
+  }
+  return dictProps === 40 ? 0xffffffff : (2 | (dictProps & 1)) * 2 ** (Math.floor(dictProps / 2) + 11);
+}
+
+/**
+ * Decompress an .xz file, yielding the uncompressed data in pieces of at
+ * most 2 MiB. Block checks are verified; concatenated streams are supported.
+ */
+function* decompressXz(filePath: string): Generator<Buffer> {
+  const fd = fs.openSync(filePath, 'r');
+  try {
+    const input = new ByteReader(fd);
+    do {
+      const streamHeader = input.bytes(12);
+      if (!streamHeader.subarray(0, 6).equals(XZ_HEADER_MAGIC)) {
+        throw corrupt('not an xz file');
+      }
+      if (crc32(streamHeader.subarray(6, 8)) !== streamHeader.readUInt32LE(8)) {
+        throw corrupt('xz stream header checksum mismatch');
+      }
+      const checkType = streamHeader[7] & 0x0f;
+
+      let blocks = 0;
+      for (let first = input.byte(); first !== 0x00; first = input.byte()) {
+        const blockStart = input.position - 1;
+        const dictSize = readXzBlockHeader(input, first);
+        const decoder = new Lzma2Decoder(input, Math.min(dictSize, 1024 * 1024 * 1024));
+        const check = new XzCheck(checkType);
+        for (let chunk = decoder.nextChunk(); chunk; chunk = decoder.nextChunk()) {
+          check.update(chunk);
+          yield chunk;
+        }
+        while ((input.position - blockStart) % 4 !== 0) {
+          if (input.byte() !== 0) {
+            throw corrupt('invalid xz block padding');
+          }
+        }
+        if (!check.matches(input.bytes(XZ_CHECK_SIZES[checkType]))) {
+          throw corrupt('xz block check mismatch');
+        }
+        blocks++;
+      }
+
+      // Index: record count, the records, padding and a CRC32
+      const indexStart = input.position - 1;
+      if (input.varint() !== blocks) {
+        throw corrupt('xz index does not match the blocks');
+      }
+      for (let i = 0; i < blocks * 2; i++) {
+        input.varint();
+      }
+      while ((input.position - indexStart) % 4 !== 0) {
+        input.byte();
+      }
+      input.bytes(4);
+
+      const footer = input.bytes(12);
+      if (!footer.subarray(10, 12).equals(XZ_FOOTER_MAGIC)) {
+        throw corrupt('invalid xz stream footer');
+      }
+
+      // Stream padding before another concatenated stream or the end
+      while (input.position < input.size) {
+        if (input.byte() !== 0) {
+          input.position--;
+          break;
+        }
+      }
+    } while (input.position < input.size);
+  } finally {
+    fs.closeSync(fd);
+  }
+}
@@ -0,0 +1,26 @@
+/**
+ * Run an async task for each item with at most `limit` tasks in flight.
+ * Results keep the order of the items; after the first failure no new tasks
+ * start and it is rethrown.
+ */
+export async function mapWithConcurrency<T, R>(
+  items: T[],
+  limit: number,
+  task: (item: T) => Promise<R>
+): Promise<R[]> {
+  const results: R[] = new Array(items.length);
+  let next = 0;
+  const workers = Array.from({ length: Math.min(limit, items.length) }, async () => {
+    while (next < items.length) {
+      const index = next++;
+      try {
+        results[index] = await task(items[index]);
+      } catch (error) {
+        next = items.length;
+        throw error;
+      }
+    }
+  });
+  await Promise.all(workers);
+  return results;
+}
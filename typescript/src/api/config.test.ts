@@ -0,0 +1,44 @@
+import { describe, expect, test } from 'bun:test';
+import { Config } from './config';
+import { accountsDb, getConfigValue, setConfigValue } from './database';
+import { encryptSecret } from './secrets';
+import { useTempDataDir } from './testing';
+
+useTempDataDir({ database: true });
+
+function hasConfigValue(key: string): boolean {
+  try {
+    getConfigValue(key);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+describe('refresh token precedence', () => {
+  test("the active account's token wins over a legacy config copy", () => {
+    setConfigValue('refresh_token', encryptSecret('legacy-token'));
+    accountsDb().addAccount({ user_id: '1', username: 'player', refresh_token: 'account-token' });
+
+    expect(Config.loadFromDb().refresh_token).toBe('account-token');
+  });
+
+  test('the legacy config copy is used while no account has a token', () => {
+    setConfigValue('refresh_token', encryptSecret('legacy-token'));
+
+    expect(Config.loadFromDb().refresh_token).toBe('legacy-token');
+  });
+
+  test('saving never writes the token and drops the legacy copy once an account holds it', () => {
+    setConfigValue('refresh_token', encryptSecret('legacy-token'));
+    const config = Config.loadFromDb();
+    config.save();
+    expect(hasConfigValue('refresh_token')).toBe(true);
+
+    accountsDb().addAccount({ user_id: '1', username: 'player', refresh_token: 'account-token' });
+    config.refresh_token = 'account-token';
+    config.save();
+    expect(hasConfigValue('refresh_token')).toBe(false);
+    expect(Config.loadFromDb().refresh_token).toBe('account-token');
+  });
+});
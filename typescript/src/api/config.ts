@@ -1,6 +1,7 @@
 import * as path from 'path';
 import * as os from 'os';
 import { ConfigDto } from './dto';
+import { decryptSecret } from './secrets';
 
 // Constants for supported download languages
 export const SUPPORTED_DOWNLOAD_LANGUAGES: [string, string][] = [
@@ -68,6 +69,11 @@ export const IGNORE_GAME_IDS: number[] = [
 
 export const MINIMUM_RESUME_SIZE: number = 20 * 1024 * 1024;
 export const DEFAULT_DOWNLOAD_THREAD_COUNT: number = 4;
+export const DEFAULT_DOWNLOAD_RETRIES: number = 3;
+// Connections per installer file; 1 downloads each file over a single stream
+export const DEFAULT_DOWNLOAD_SEGMENTS: number = 1;
+export const DEFAULT_REQUEST_TIMEOUT_SECS: number = 30;
+export const DEFAULT_LOG_LEVEL: string = 'info';
 
 export const BINARY_NAMES_TO_IGNORE: string[] = [
   'unins000.exe',
@@ -94,16 +100,34 @@ export class Config {
   show_windows_games: boolean = false;
   keep_window_maximized: boolean = false;
   installed_filter: boolean = false;
+  sort_installed_first: boolean = true;
   create_applications_file: boolean = false;
+  // Append the game id to new install directories, e.g. "Name (1234567890)"
+  install_dir_include_id: boolean = false;
   max_parallel_game_downloads: number = DEFAULT_DOWNLOAD_THREAD_COUNT;
+  max_download_retries: number = DEFAULT_DOWNLOAD_RETRIES;
+  download_segments: number = DEFAULT_DOWNLOAD_SEGMENTS;
+  request_timeout_secs: number = DEFAULT_REQUEST_TIMEOUT_SECS;
+  // Lowest level written to the log file: error, warn, info or debug
+  log_level: string = DEFAULT_LOG_LEVEL;
+  proxy_url: string = '';
+  // Extra executable names (or globs like *CrashHandler*) never picked to launch
+  ignored_binaries: string[] = [];
   current_downloads: number[] = [];
   paused_downloads: Map<string, number> = new Map();
   active_account_id?: string;
   wine_prefix: string = '';
   wine_executable: string = '';
   wine_debug: boolean = false;
+  // WINEDEBUG channels used while wine_debug is on, e.g. "fixme-all,err+all"; +all when empty
+  wine_debug_channels: string = '';
   wine_disable_ntsync: boolean = false;
   wine_auto_install_dxvk: boolean = true;
+  // Custom DOSBox/ScummVM binaries, e.g. dosbox-staging; PATH is searched when empty
+  dosbox_executable: string = '';
+  scummvm_executable: string = '';
+  interactive_install: boolean = false;
+  verify_downloads: boolean = true;
 
   constructor() {
     this.install_dir = getDefaultInstallDir();
@@ -120,12 +144,43 @@ export class Config {
       try { config.view = getConfigValue('view'); } catch (e) {}
       try { config.install_dir = getConfigValue('install_dir'); } catch (e) {}
       try { config.username = getConfigValue('username'); } catch (e) {}
-      try { config.refresh_token = getConfigValue('refresh_token'); } catch (e) {}
+      config.refresh_token = loadRefreshToken();
       try { config.keep_installers = getConfigValue('keep_installers') === 'true'; } catch (e) {}
       try { config.stay_logged_in = getConfigValue('stay_logged_in') === 'true'; } catch (e) {}
       try { config.use_dark_theme = getConfigValue('use_dark_theme') === 'true'; } catch (e) {}
       try { config.show_hidden_games = getConfigValue('show_hidden_games') === 'true'; } catch (e) {}
       try { config.show_windows_games = getConfigValue('show_windows_games') === 'true'; } catch (e) {}
+      try { config.keep_window_maximized = getConfigValue('keep_window_maximized') === 'true'; } catch (e) {}
+      try { config.installed_filter = getConfigValue('installed_filter') === 'true'; } catch (e) {}
+      try { config.sort_installed_first = getConfigValue('sort_installed_first') !== 'false'; } catch (e) {}
+      try { config.create_applications_file = getConfigValue('create_applications_file') === 'true'; } catch (e) {}
+      try { config.install_dir_include_id = getConfigValue('install_dir_include_id') === 'true'; } catch (e) {}
+      try {
+        const val = parseInt(getConfigValue('max_parallel_game_downloads'), 10);
+        if (val > 0) config.max_parallel_game_downloads = val;
+      } catch (e) {}
+      try {
+        const val = parseInt(getConfigValue('max_download_retries'), 10);
+        if (val >= 0) config.max_download_retries = val;
+      } catch (e) {}
+      try {
+        const val = parseInt(getConfigValue('download_segments'), 10);
+        if (val > 0) config.download_segments = val;
+      } catch (e) {}
+      try { config.log_level = getConfigValue('log_level'); } catch (e) {}
+      try {
+        const val = parseInt(getConfigValue('request_timeout_secs'), 10);
+        if (val > 0) config.request_timeout_secs = val;
+      } catch (e) {}
+      try { config.proxy_url = getConfigValue('proxy_url'); } catch (e) {}
+      try {
+        const val = getConfigValue('ignored_binaries');
+        if (val) config.ignored_binaries = JSON.parse(val);
+      } catch (e) {}
+      try {
+        const val = getConfigValue('paused_downloads');
+        if (val) config.paused_downloads = new Map(Object.entries(JSON.parse(val)));
+      } catch (e) {}
       try {
         const val = getConfigValue('active_account_id');
         config.active_account_id = val ? val : undefined;
@@ -134,8 +189,13 @@ export class Config {
       try { config.wine_prefix = getConfigValue('wine_prefix'); } catch (e) {}
       try { config.wine_executable = getConfigValue('wine_executable'); } catch (e) {}
       try { config.wine_debug = getConfigValue('wine_debug') === 'true'; } catch (e) {}
+      try { config.wine_debug_channels = getConfigValue('wine_debug_channels'); } catch (e) {}
       try { config.wine_disable_ntsync = getConfigValue('wine_disable_ntsync') === 'true'; } catch (e) {}
       try { config.wine_auto_install_dxvk = getConfigValue('wine_auto_install_dxvk') !== 'false'; } catch (e) {}
+      try { config.dosbox_executable = getConfigValue('dosbox_executable'); } catch (e) {}
+      try { config.scummvm_executable = getConfigValue('scummvm_executable'); } catch (e) {}
+      try { config.interactive_install = getConfigValue('interactive_install') === 'true'; } catch (e) {}
+      try { config.verify_downloads = getConfigValue('verify_downloads') !== 'false'; } catch (e) {}
     } catch (e) {
       // Database not available, use defaults
     }
@@ -149,26 +209,49 @@ export class Config {
 
   saveToDb(): void {
     try {
-      const { setConfigValue } = require('./database');
+      const { setConfigValue, deleteConfigValue, accountsDb } = require('./database');
       
       setConfigValue('locale', this.locale);
       setConfigValue('lang', this.lang);
       setConfigValue('view', this.view);
       setConfigValue('install_dir', this.install_dir);
       setConfigValue('username', this.username);
-      setConfigValue('refresh_token', this.refresh_token);
+      // The refresh token is never written here, it belongs to the accounts
+      // table. A legacy copy is only dropped once an account holds the token,
+      // so upgrading from a version without accounts doesn't log the user out.
+      if (accountsDb().getActiveAccount()?.refresh_token) {
+        deleteConfigValue('refresh_token');
+      }
       setConfigValue('keep_installers', this.keep_installers ? 'true' : 'false');
       setConfigValue('stay_logged_in', this.stay_logged_in ? 'true' : 'false');
       setConfigValue('use_dark_theme', this.use_dark_theme ? 'true' : 'false');
       setConfigValue('show_hidden_games', this.show_hidden_games ? 'true' : 'false');
       setConfigValue('show_windows_games', this.show_windows_games ? 'true' : 'false');
+      setConfigValue('keep_window_maximized', this.keep_window_maximized ? 'true' : 'false');
+      setConfigValue('installed_filter', this.installed_filter ? 'true' : 'false');
+      setConfigValue('sort_installed_first', this.sort_installed_first ? 'true' : 'false');
+      setConfigValue('create_applications_file', this.create_applications_file ? 'true' : 'false');
+      setConfigValue('install_dir_include_id', this.install_dir_include_id ? 'true' : 'false');
+      setConfigValue('max_parallel_game_downloads', String(this.max_parallel_game_downloads));
+      setConfigValue('max_download_retries', String(this.max_download_retries));
+      setConfigValue('download_segments', String(this.download_segments));
+      setConfigValue('request_timeout_secs', String(this.request_timeout_secs));
+      setConfigValue('log_level', this.log_level);
+      setConfigValue('proxy_url', this.proxy_url);
+      setConfigValue('ignored_binaries', JSON.stringify(this.ignored_binaries));
+      setConfigValue('paused_downloads', JSON.stringify(Object.fromEntries(this.paused_downloads)));
       setConfigValue('active_account_id', this.active_account_id || '');
       // Wine settings
       setConfigValue('wine_prefix', this.wine_prefix);
       setConfigValue('wine_executable', this.wine_executable);
       setConfigValue('wine_debug', this.wine_debug ? 'true' : 'false');
+      setConfigValue('wine_debug_channels', this.wine_debug_channels);
       setConfigValue('wine_disable_ntsync', this.wine_disable_ntsync ? 'true' : 'false');
       setConfigValue('wine_auto_install_dxvk', this.wine_auto_install_dxvk ? 'true' : 'false');
+      setConfigValue('dosbox_executable', this.dosbox_executable);
+      setConfigValue('scummvm_executable', this.scummvm_executable);
+      setConfigValue('interactive_install', this.interactive_install ? 'true' : 'false');
+      setConfigValue('verify_downloads', this.verify_downloads ? 'true' : 'false');
     } catch (e) {
       // Database not available
     }
@@ -208,10 +291,34 @@ export class Config {
       wine_debug: this.wine_debug,
       wine_disable_ntsync: this.wine_disable_ntsync,
       wine_auto_install_dxvk: this.wine_auto_install_dxvk,
+      interactive_install: this.interactive_install,
+      verify_downloads: this.verify_downloads,
     };
   }
 }
 
+/**
+ * The refresh token of the active account always wins. The legacy
+ * refresh_token config key is only read when no account has a token, i.e.
+ * right after upgrading from a version that stored it there.
+ */
+function loadRefreshToken(): string {
+  const { getConfigValue, accountsDb } = require('./database');
+
+  try {
+    const account = accountsDb().getActiveAccount();
+    if (account?.refresh_token) {
+      return account.refresh_token;
+    }
+  } catch (e) {}
+
+  try {
+    return decryptSecret(getConfigValue('refresh_token'));
+  } catch (e) {
+    return '';
+  }
+}
+
 function getDefaultInstallDir(): string {
   const homeDir = os.homedir();
   return path.join(homeDir, 'GOG Games');
@@ -235,6 +342,26 @@ export function getThumbnailDir(): string {
   return path.join(getCacheDir(), 'thumbnails');
 }
 
+export function getCoverArtDir(): string {
+  return path.join(getCacheDir(), 'covers');
+}
+
+export function getLogDir(): string {
+  return path.join(getCacheDir(), 'logs');
+}
+
+/**
+ * Save games backed up by uninstallGameKeepSaves, one directory per game id
+ */
+export function getSavesBackupDir(): string {
+  return path.join(getDataDir(), 'saves');
+}
+
 export function getConfigGamesDir(): string {
   return path.join(getDataDir(), 'games');
 }
+
+export function getMinigalaxyConfigDir(): string {
+  const configDir = process.env.XDG_CONFIG_HOME || path.join(os.homedir(), '.config');
+  return path.join(configDir, 'minigalaxy');
+}
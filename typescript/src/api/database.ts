@@ -3,7 +3,8 @@ import * as path from 'path';
 import * as fs from 'fs';
 import { getDataDir } from './config';
 import { GalaxiError, GalaxiErrorType } from './error';
-import { AccountDto, GameDto } from './dto';
+import { AccountDto, DlcDto, GameDto } from './dto';
+import { encryptSecret, decryptSecret, isEncrypted } from './secrets';
 
 let db: Database | null = null;
 
@@ -22,66 +23,8 @@ export function initDatabase(): void {
   
   db = new Database(dbPath);
   
-  // Create tables
-  db.exec(`
-    -- Configuration table
-    CREATE TABLE IF NOT EXISTS config (
-      key TEXT PRIMARY KEY,
-      value TEXT NOT NULL
-    );
-    
-    -- Accounts table
-    CREATE TABLE IF NOT EXISTS accounts (
-      user_id TEXT PRIMARY KEY,
-      username TEXT NOT NULL,
-      email TEXT,
-      avatar_url TEXT,
-      refresh_token TEXT NOT NULL,
-      added_at TEXT NOT NULL,
-      last_login TEXT,
-      is_active INTEGER DEFAULT 0
-    );
-    
-    -- Games cache table
-    CREATE TABLE IF NOT EXISTS games (
-      id INTEGER PRIMARY KEY,
-      name TEXT NOT NULL,
-      url TEXT,
-      install_dir TEXT,
-      image_url TEXT,
-      platform TEXT,
-      category TEXT,
-      version TEXT,
-      last_updated TEXT
-    );
-    
-    -- Downloads table
-    CREATE TABLE IF NOT EXISTS downloads (
-      game_id INTEGER PRIMARY KEY,
-      status TEXT NOT NULL,
-      progress REAL DEFAULT 0,
-      paused_at TEXT
-    );
-    
-    -- DLCs table
-    CREATE TABLE IF NOT EXISTS dlcs (
-      id INTEGER PRIMARY KEY,
-      game_id INTEGER NOT NULL,
-      name TEXT NOT NULL,
-      title TEXT,
-      image_url TEXT,
-      installed INTEGER DEFAULT 0,
-      FOREIGN KEY (game_id) REFERENCES games(id)
-    );
-    
-    -- Game playtime tracking table
-    CREATE TABLE IF NOT EXISTS game_playtime (
-      game_id INTEGER PRIMARY KEY,
-      total_playtime_seconds INTEGER DEFAULT 0,
-      last_played TEXT,
-      FOREIGN KEY (game_id) REFERENCES games(id)
-    );
-  `);
+  runMigrations(db);
+  encryptStoredTokens(db);
   
   // Insert default config values if not exists
   const defaultInstallDir = path.join(require('os').homedir(), 'GOG Games');
@@ -101,6 +44,8 @@ export function initDatabase(): void {
     ['wine_debug', 'false'],
     ['wine_disable_ntsync', 'false'],
     ['wine_auto_install_dxvk', 'true'],
+    ['interactive_install', 'false'],
+    ['verify_downloads', 'true'],
   ];
   
   const insertStmt = db.prepare('INSERT OR IGNORE INTO config (key, value) VALUES (?, ?)');
@@ -109,6 +54,290 @@ export function initDatabase(): void {
   }
 }
 
+interface Migration {
+  version: number;
+  description: string;
+  up: (db: Database) => void;
+}
+
+/**
+ * Schema changes in order. The database records the last one applied in
+ * PRAGMA user_version; add new steps at the end and never edit old ones.
+ * Steps must tolerate databases created before versioning existed, which
+ * report version 0 but may already have some of the tables and columns.
+ */
+const MIGRATIONS: Migration[] = [
+  {
+    version: 1,
+    description: 'initial schema',
+    up: db => db.exec(`
+      -- Configuration table
+      CREATE TABLE IF NOT EXISTS config (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+      );
+    
+      -- Accounts table
+      CREATE TABLE IF NOT EXISTS accounts (
+        user_id TEXT PRIMARY KEY,
+        username TEXT NOT NULL,
+        email TEXT,
+        avatar_url TEXT,
+        refresh_token TEXT NOT NULL,
+        added_at TEXT NOT NULL,
+        last_login TEXT,
+        is_active INTEGER DEFAULT 0
+      );
+    
+      -- Games cache table
+      CREATE TABLE IF NOT EXISTS games (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        url TEXT,
+        install_dir TEXT,
+        image_url TEXT,
+        platform TEXT,
+        category TEXT,
+        version TEXT,
+        last_updated TEXT
+      );
+    
+      -- Downloads table
+      CREATE TABLE IF NOT EXISTS downloads (
+        game_id INTEGER PRIMARY KEY,
+        status TEXT NOT NULL,
+        progress REAL DEFAULT 0,
+        paused_at TEXT
+      );
+    
+      -- DLCs table
+      CREATE TABLE IF NOT EXISTS dlcs (
+        id INTEGER PRIMARY KEY,
+        game_id INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        title TEXT,
+        image_url TEXT,
+        installed INTEGER DEFAULT 0,
+        FOREIGN KEY (game_id) REFERENCES games(id)
+      );
+    
+      -- Game playtime tracking table
+      CREATE TABLE IF NOT EXISTS game_playtime (
+        game_id INTEGER PRIMARY KEY,
+        total_playtime_seconds INTEGER DEFAULT 0,
+        last_played TEXT,
+        FOREIGN KEY (game_id) REFERENCES games(id)
+      );
+    `),
+  },
+  {
+    version: 2,
+    description: 'byte counts for resumable downloads',
+    up: db => {
+      addColumnIfMissing(db, 'downloads', 'downloaded_bytes', 'INTEGER DEFAULT 0');
+      addColumnIfMissing(db, 'downloads', 'total_bytes', 'INTEGER DEFAULT 0');
+    },
+  },
+  {
+    version: 3,
+    description: 'running game sessions',
+    up: db => db.exec(`
+      -- Sessions of games that are currently running, so playtime survives a crash
+      CREATE TABLE IF NOT EXISTS game_sessions (
+        game_id INTEGER PRIMARY KEY,
+        started_at INTEGER NOT NULL,
+        last_seen INTEGER NOT NULL
+      );
+    `),
+  },
+  {
+    version: 4,
+    description: 'hidden games',
+    up: db => addColumnIfMissing(db, 'games', 'hidden', 'INTEGER DEFAULT 0'),
+  },
+  {
+    version: 5,
+    description: 'cached installer manifest',
+    up: db => db.exec(`
+      -- Completed installer files kept in the downloads directory, so a
+      -- cached file is only reused for the version it was downloaded for
+      CREATE TABLE IF NOT EXISTS cached_installers (
+        path TEXT PRIMARY KEY,
+        game_id INTEGER NOT NULL,
+        version TEXT,
+        size INTEGER NOT NULL,
+        md5 TEXT,
+        downloaded_at TEXT NOT NULL
+      );
+    `),
+  },
+  {
+    version: 6,
+    description: 'partial file of each download',
+    up: db => addColumnIfMissing(db, 'downloads', 'partial_path', 'TEXT'),
+  },
+  {
+    version: 7,
+    description: 'favorite games',
+    up: db => addColumnIfMissing(db, 'games', 'favorite', 'INTEGER DEFAULT 0'),
+  },
+  {
+    version: 8,
+    description: 'game tags',
+    up: db => db.exec(`
+      -- User-defined tags for grouping the library; a game can have many.
+      -- Names compare case-insensitively so "RPG" and "rpg" are one tag.
+      -- Foreign keys aren't enforced, so clearGames deletes tags itself.
+      CREATE TABLE IF NOT EXISTS game_tags (
+        game_id INTEGER NOT NULL,
+        tag TEXT NOT NULL COLLATE NOCASE,
+        PRIMARY KEY (game_id, tag)
+      );
+    `),
+  },
+  {
+    version: 9,
+    description: 'what each download was started for',
+    up: db => {
+      addColumnIfMissing(db, 'downloads', 'parent_id', 'INTEGER');
+      addColumnIfMissing(db, 'downloads', 'language', 'TEXT');
+    },
+  },
+];
+
+/**
+ * Apply every migration newer than the database's schema version, each in
+ * its own transaction together with the version bump.
+ */
+function runMigrations(db: Database): void {
+  const { user_version: current } = db.prepare('PRAGMA user_version').get() as { user_version: number };
+  for (const migration of MIGRATIONS) {
+    if (migration.version <= current) {
+      continue;
+    }
+    db.transaction(() => {
+      migration.up(db);
+      db.exec(`PRAGMA user_version = ${migration.version}`);
+    })();
+    console.log(`Applied database migration ${migration.version}: ${migration.description}`);
+  }
+}
+
+// Schema version a fully migrated database reports
+export const SCHEMA_VERSION = MIGRATIONS[MIGRATIONS.length - 1].version;
+
+// Tables a database must have to be usable as a backup
+const REQUIRED_TABLES = ['config', 'accounts', 'games'];
+
+/**
+ * Write a consistent copy of the database to `destPath`. VACUUM INTO reads
+ * within a single transaction, so writes made meanwhile (e.g. download
+ * progress) don't tear the copy.
+ */
+export function exportDatabase(destPath: string): void {
+  const db = getDb();
+  const dbPath = getDbPath();
+  if (
+    path.resolve(destPath) === path.resolve(dbPath) ||
+    (fs.existsSync(destPath) && fs.realpathSync(destPath) === fs.realpathSync(dbPath))
+  ) {
+    throw new GalaxiError('Cannot export the database onto itself', GalaxiErrorType.FileSystemError);
+  }
+  if (fs.existsSync(destPath)) {
+    fs.rmSync(destPath);
+  }
+  fs.mkdirSync(path.dirname(destPath), { recursive: true });
+  db.prepare('VACUUM INTO ?').run(destPath);
+}
+
+/**
+ * Check that a file is a Galaxi database this version can migrate
+ */
+function validateBackup(srcPath: string): void {
+  let backup: Database;
+  try {
+    backup = new Database(srcPath, { readonly: true });
+  } catch (error: any) {
+    throw new GalaxiError(`Cannot open ${srcPath}: ${error.message}`, GalaxiErrorType.FileSystemError);
+  }
+  try {
+    const { integrity_check: integrity } = backup.prepare('PRAGMA integrity_check').get() as { integrity_check: string };
+    if (integrity !== 'ok') {
+      throw new GalaxiError(`Backup is corrupt: ${integrity}`, GalaxiErrorType.ConfigError);
+    }
+    const { user_version: version } = backup.prepare('PRAGMA user_version').get() as { user_version: number };
+    if (version > SCHEMA_VERSION) {
+      throw new GalaxiError(
+        `Backup is from a newer Galaxi (schema ${version}, this version supports ${SCHEMA_VERSION})`,
+        GalaxiErrorType.ConfigError
+      );
+    }
+    const tables = new Set(
+      (backup.prepare("SELECT name FROM sqlite_master WHERE type = 'table'").all() as { name: string }[]).map(t => t.name)
+    );
+    const missing = REQUIRED_TABLES.filter(t => !tables.has(t));
+    if (missing.length > 0) {
+      throw new GalaxiError(`Not a Galaxi database (missing ${missing.join(', ')})`, GalaxiErrorType.ConfigError);
+    }
+  } catch (error: any) {
+    if (error instanceof GalaxiError) {
+      throw error;
+    }
+    throw new GalaxiError(`Not a valid database: ${error.message}`, GalaxiErrorType.ConfigError);
+  } finally {
+    backup.close();
+  }
+}
+
+/**
+ * Replace the database with a backup made by exportDatabase. The backup is
+ * validated first, then copied into place and migrated to the current schema.
+ */
+export function importDatabase(srcPath: string): void {
+  validateBackup(srcPath);
+
+  const dbPath = getDbPath();
+  const staging = `${dbPath}.import`;
+  fs.copyFileSync(srcPath, staging);
+
+  getDb().close();
+  db = null;
+  for (const suffix of ['-wal', '-shm']) {
+    fs.rmSync(`${dbPath}${suffix}`, { force: true });
+  }
+  fs.renameSync(staging, dbPath);
+  initDatabase();
+}
+
+/**
+ * Encrypt refresh tokens still stored in plaintext by older versions
+ */
+function encryptStoredTokens(db: Database): void {
+  try {
+    const accounts = db.prepare('SELECT user_id, refresh_token FROM accounts').all() as { user_id: string; refresh_token: string }[];
+    const update = db.prepare('UPDATE accounts SET refresh_token = ? WHERE user_id = ?');
+    for (const account of accounts) {
+      if (account.refresh_token && !isEncrypted(account.refresh_token)) {
+        update.run(encryptSecret(account.refresh_token), account.user_id);
+      }
+    }
+
+    const config = db.prepare("SELECT value FROM config WHERE key = 'refresh_token'").get() as { value: string } | undefined;
+    if (config?.value && !isEncrypted(config.value)) {
+      db.prepare("UPDATE config SET value = ? WHERE key = 'refresh_token'").run(encryptSecret(config.value));
+    }
+  } catch (error) {
+    console.error('Failed to encrypt stored refresh tokens:', error);
+  }
+}
+
+function addColumnIfMissing(db: Database, table: string, column: string, definition: string): void {
+  const columns = db.prepare(`PRAGMA table_info(${table})`).all() as { name: string }[];
+  if (!columns.some(c => c.name === column)) {
+    db.exec(`ALTER TABLE ${table} ADD COLUMN ${column} ${definition}`);
+  }
+}
+
 function getDb(): Database {
   if (!db) {
     throw new GalaxiError('Database not initialized', GalaxiErrorType.ConfigError);
@@ -130,6 +359,11 @@ export function setConfigValue(key: string, value: string): void {
   db.prepare('INSERT OR REPLACE INTO config (key, value) VALUES (?, ?)').run(key, value);
 }
 
+export function deleteConfigValue(key: string): void {
+  const db = getDb();
+  db.prepare('DELETE FROM config WHERE key = ?').run(key);
+}
+
 // Account management
 export function accountsDb() {
   return {
@@ -145,7 +379,7 @@ export function accountsDb() {
         account.username,
         null,
         account.avatar_url || null,
-        account.refresh_token,
+        encryptSecret(account.refresh_token),
         now,
         now,
         1
@@ -165,7 +399,7 @@ export function accountsDb() {
         user_id: row.user_id,
         username: row.username,
         avatar_url: row.avatar_url,
-        refresh_token: row.refresh_token,
+        refresh_token: decryptSecret(row.refresh_token),
       };
     },
 
@@ -180,7 +414,7 @@ export function accountsDb() {
         user_id: row.user_id,
         username: row.username,
         avatar_url: row.avatar_url,
-        refresh_token: row.refresh_token,
+        refresh_token: decryptSecret(row.refresh_token),
       }));
     },
 
@@ -197,7 +431,7 @@ export function accountsDb() {
         user_id: row.user_id,
         username: row.username,
         avatar_url: row.avatar_url,
-        refresh_token: row.refresh_token,
+        refresh_token: decryptSecret(row.refresh_token),
       };
     },
 
@@ -212,6 +446,16 @@ export function accountsDb() {
       db.prepare('DELETE FROM accounts WHERE user_id = ?').run(userId);
     },
 
+    updateRefreshToken(userId: string, refreshToken: string): void {
+      const db = getDb();
+      db.prepare('UPDATE accounts SET refresh_token = ? WHERE user_id = ?').run(encryptSecret(refreshToken), userId);
+    },
+
+    clearRefreshTokens(): void {
+      const db = getDb();
+      db.prepare("UPDATE accounts SET refresh_token = ''").run();
+    },
+
     updateAvatar(userId: string, avatarUrl: string): void {
       const db = getDb();
       db.prepare('UPDATE accounts SET avatar_url = ? WHERE user_id = ?').run(avatarUrl, userId);
@@ -225,10 +469,19 @@ export function gamesDb() {
     saveGame(game: GameDto): void {
       const db = getDb();
       const now = new Date().toISOString();
+      // Upsert rather than replace so local-only columns like hidden and favorite are kept
       db.prepare(`
-        INSERT OR REPLACE INTO games 
+        INSERT INTO games 
         (id, name, url, install_dir, image_url, platform, category, last_updated)
         VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(id) DO UPDATE SET
+          name = excluded.name,
+          url = excluded.url,
+          install_dir = excluded.install_dir,
+          image_url = excluded.image_url,
+          platform = excluded.platform,
+          category = excluded.category,
+          last_updated = excluded.last_updated
       `).run(
         game.id,
         game.name,
@@ -240,15 +493,7 @@ export function gamesDb() {
         now
       );
       
-      // Save DLCs
-      db.prepare('DELETE FROM dlcs WHERE game_id = ?').run(game.id);
-      const dlcStmt = db.prepare(`
-        INSERT INTO dlcs (id, game_id, name, title, image_url)
-        VALUES (?, ?, ?, ?, ?)
-      `);
-      for (const dlc of game.dlcs) {
-        dlcStmt.run(dlc.id, game.id, dlc.name, dlc.title, dlc.image_url);
-      }
+      dlcsDb().saveDlcs(game.id, game.dlcs);
     },
 
     getGame(gameId: number): GameDto | null {
@@ -260,11 +505,6 @@ export function gamesDb() {
       
       if (!row) return null;
       
-      const dlcs = db.prepare(`
-        SELECT id, name, title, image_url
-        FROM dlcs WHERE game_id = ?
-      `).all(gameId) as any[];
-      
       return {
         id: row.id,
         name: row.name,
@@ -273,12 +513,7 @@ export function gamesDb() {
         image_url: row.image_url,
         platform: row.platform,
         category: row.category,
-        dlcs: dlcs.map(d => ({
-          id: d.id,
-          name: d.name,
-          title: d.title,
-          image_url: d.image_url,
-        })),
+        dlcs: dlcsDb().getDlcs(gameId),
       };
     },
 
@@ -289,34 +524,138 @@ export function gamesDb() {
         FROM games ORDER BY name
       `).all() as any[];
       
-      return rows.map(row => {
-        const dlcs = db.prepare(`
-          SELECT id, name, title, image_url
-          FROM dlcs WHERE game_id = ?
-        `).all(row.id) as any[];
-        
-        return {
-          id: row.id,
-          name: row.name,
-          url: row.url,
-          install_dir: row.install_dir,
-          image_url: row.image_url,
-          platform: row.platform,
-          category: row.category,
-          dlcs: dlcs.map(d => ({
-            id: d.id,
-            name: d.name,
-            title: d.title,
-            image_url: d.image_url,
-          })),
-        };
-      });
+      return rows.map(row => ({
+        id: row.id,
+        name: row.name,
+        url: row.url,
+        install_dir: row.install_dir,
+        image_url: row.image_url,
+        platform: row.platform,
+        category: row.category,
+        dlcs: dlcsDb().getDlcs(row.id),
+      }));
+    },
+
+    setHidden(gameId: number, hidden: boolean): void {
+      const db = getDb();
+      db.prepare('UPDATE games SET hidden = ? WHERE id = ?').run(hidden ? 1 : 0, gameId);
+    },
+
+    getHiddenGameIds(): Set<number> {
+      const db = getDb();
+      const rows = db.prepare('SELECT id FROM games WHERE hidden = 1').all() as { id: number }[];
+      return new Set(rows.map(r => r.id));
+    },
+
+    setFavorite(gameId: number, favorite: boolean): void {
+      const db = getDb();
+      db.prepare('UPDATE games SET favorite = ? WHERE id = ?').run(favorite ? 1 : 0, gameId);
+    },
+
+    getFavoriteGameIds(): Set<number> {
+      const db = getDb();
+      const rows = db.prepare('SELECT id FROM games WHERE favorite = 1').all() as { id: number }[];
+      return new Set(rows.map(r => r.id));
     },
 
     clearGames(): void {
       const db = getDb();
       db.prepare('DELETE FROM games').run();
       db.prepare('DELETE FROM dlcs').run();
+      db.prepare('DELETE FROM game_tags').run();
+    },
+  };
+}
+
+// DLCs known for each game, with whether they were installed through Galaxi
+export function dlcsDb() {
+  return {
+    /**
+     * Replace a game's DLC list. The installed flag of DLCs that are still
+     * listed is kept.
+     */
+    saveDlcs(gameId: number, dlcs: DlcDto[]): void {
+      const db = getDb();
+      const upsert = db.prepare(`
+        INSERT INTO dlcs (id, game_id, name, title, image_url)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT(id) DO UPDATE SET
+          game_id = excluded.game_id,
+          name = excluded.name,
+          title = excluded.title,
+          image_url = excluded.image_url
+      `);
+      db.transaction(() => {
+        const ids = dlcs.map(d => d.id);
+        db.prepare(
+          `DELETE FROM dlcs WHERE game_id = ? AND id NOT IN (${ids.map(() => '?').join(', ')})`
+        ).run(gameId, ...ids);
+        for (const dlc of dlcs) {
+          upsert.run(dlc.id, gameId, dlc.name, dlc.title, dlc.image_url);
+        }
+      })();
+    },
+
+    getDlcs(gameId: number): DlcDto[] {
+      const db = getDb();
+      const rows = db.prepare(`
+        SELECT id, name, title, image_url, installed
+        FROM dlcs WHERE game_id = ?
+      `).all(gameId) as any[];
+      return rows.map(d => ({
+        id: d.id,
+        name: d.name,
+        title: d.title,
+        image_url: d.image_url,
+        installed: d.installed === 1,
+      }));
+    },
+
+    setDlcInstalled(dlcId: number, installed: boolean): void {
+      const db = getDb();
+      db.prepare('UPDATE dlcs SET installed = ? WHERE id = ?').run(installed ? 1 : 0, dlcId);
+    },
+  };
+}
+
+// Library tags
+export function tagsDb() {
+  return {
+    addTag(gameId: number, tag: string): void {
+      const db = getDb();
+      db.prepare('INSERT OR IGNORE INTO game_tags (game_id, tag) VALUES (?, ?)').run(gameId, tag);
+    },
+
+    removeTag(gameId: number, tag: string): void {
+      const db = getDb();
+      db.prepare('DELETE FROM game_tags WHERE game_id = ? AND tag = ?').run(gameId, tag);
+    },
+
+    getTags(gameId: number): string[] {
+      const db = getDb();
+      const rows = db.prepare('SELECT tag FROM game_tags WHERE game_id = ? ORDER BY tag').all(gameId) as { tag: string }[];
+      return rows.map(r => r.tag);
+    },
+
+    /**
+     * Tags of every tagged game, for listing the library in one query
+     */
+    getAllTags(): Map<number, string[]> {
+      const db = getDb();
+      const rows = db.prepare('SELECT game_id, tag FROM game_tags ORDER BY tag').all() as { game_id: number; tag: string }[];
+      const tags = new Map<number, string[]>();
+      for (const row of rows) {
+        const gameTags = tags.get(row.game_id) ?? [];
+        gameTags.push(row.tag);
+        tags.set(row.game_id, gameTags);
+      }
+      return tags;
+    },
+
+    getGameIdsByTag(tag: string): number[] {
+      const db = getDb();
+      const rows = db.prepare('SELECT game_id FROM game_tags WHERE tag = ?').all(tag) as { game_id: number }[];
+      return rows.map(r => r.game_id);
     },
   };
 }
@@ -324,9 +663,9 @@ export function gamesDb() {
 // Playtime tracking
 export function playtimeDb() {
   return {
-    savePlaytime(gameId: number, sessionDurationSeconds: number): void {
+    savePlaytime(gameId: number, sessionDurationSeconds: number, lastPlayed?: string): void {
       const db = getDb();
-      const now = new Date().toISOString();
+      const now = lastPlayed ?? new Date().toISOString();
       
       // Get current playtime
       const currentRow = db.prepare(
@@ -354,6 +693,142 @@ export function playtimeDb() {
       
       return row?.total_playtime_seconds || 0;
     },
+
+    getPlaytime(gameId: number): { total_playtime_seconds: number; last_played: string | null } | undefined {
+      const db = getDb();
+      return db.prepare(
+        'SELECT total_playtime_seconds, last_played FROM game_playtime WHERE game_id = ?'
+      ).get(gameId) as { total_playtime_seconds: number; last_played: string | null } | undefined;
+    },
+    
+    beginSession(gameId: number, startedAt: number): void {
+      const db = getDb();
+      db.prepare(
+        'INSERT OR REPLACE INTO game_sessions (game_id, started_at, last_seen) VALUES (?, ?, ?)'
+      ).run(gameId, startedAt, startedAt);
+    },
+    
+    touchSession(gameId: number, lastSeen: number): void {
+      const db = getDb();
+      db.prepare('UPDATE game_sessions SET last_seen = ? WHERE game_id = ?').run(lastSeen, gameId);
+    },
+    
+    endSession(gameId: number): void {
+      const db = getDb();
+      db.prepare('DELETE FROM game_sessions WHERE game_id = ?').run(gameId);
+    },
+    
+    // Sessions left behind when galaxi exited without ending them. Each one is
+    // credited up to the last time the game was seen running, then removed.
+    recoverSessions(): number[] {
+      const db = getDb();
+      const rows = db.prepare(
+        'SELECT game_id, started_at, last_seen FROM game_sessions'
+      ).all() as { game_id: number; started_at: number; last_seen: number }[];
+      
+      for (const row of rows) {
+        const seconds = Math.max(0, Math.floor((row.last_seen - row.started_at) / 1000));
+        this.savePlaytime(row.game_id, seconds, new Date(row.last_seen).toISOString());
+        this.endSession(row.game_id);
+      }
+      return rows.map(r => r.game_id);
+    },
+  };
+}
+
+export interface CachedInstaller {
+  path: string;
+  game_id: number; // the game or DLC the installer belongs to
+  version: string | null;
+  size: number;
+  md5: string | null;
+  downloaded_at: string;
+}
+
+// Manifest of installer files in the downloads directory
+export function installersDb() {
+  return {
+    saveInstaller(installer: Omit<CachedInstaller, 'downloaded_at'>): void {
+      const db = getDb();
+      db.prepare(`
+        INSERT OR REPLACE INTO cached_installers (path, game_id, version, size, md5, downloaded_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+      `).run(
+        installer.path,
+        installer.game_id,
+        installer.version,
+        installer.size,
+        installer.md5,
+        new Date().toISOString()
+      );
+    },
+
+    getInstaller(filePath: string): CachedInstaller | null {
+      const db = getDb();
+      return (db.prepare('SELECT * FROM cached_installers WHERE path = ?').get(filePath) as CachedInstaller) ?? null;
+    },
+
+    getAllInstallers(): CachedInstaller[] {
+      const db = getDb();
+      return db.prepare('SELECT * FROM cached_installers ORDER BY game_id, downloaded_at').all() as CachedInstaller[];
+    },
+
+    removeInstaller(filePath: string): void {
+      const db = getDb();
+      db.prepare('DELETE FROM cached_installers WHERE path = ?').run(filePath);
+    },
+  };
+}
+
+export interface StoredDownload {
+  game_id: number;
+  status: string;
+  progress: number;
+  downloaded_bytes: number;
+  total_bytes: number;
+  paused_at: string | null;
+  // Partial file of the part being downloaded, kept for resuming
+  partial_path: string | null;
+  // The game a DLC download belongs to, null for a game's own download
+  parent_id: number | null;
+  // Language explicitly chosen for the download, null for the configured one
+  language: string | null;
+}
+
+// Download state persistence
+export function downloadsDb() {
+  return {
+    saveDownload(
+      gameId: number,
+      status: string,
+      downloadedBytes: number,
+      totalBytes: number,
+      partialPath: string | null = null,
+      parentId: number | null = null,
+      language: string | null = null
+    ): void {
+      const db = getDb();
+      const progress = totalBytes > 0 ? downloadedBytes / totalBytes : 0;
+      const pausedAt = status === 'Paused' ? new Date().toISOString() : null;
+      db.prepare(`
+        INSERT OR REPLACE INTO downloads
+        (game_id, status, progress, downloaded_bytes, total_bytes, paused_at, partial_path, parent_id, language)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+      `).run(gameId, status, progress, downloadedBytes, totalBytes, pausedAt, partialPath, parentId, language);
+    },
+
+    getAllDownloads(): StoredDownload[] {
+      const db = getDb();
+      return db.prepare(`
+        SELECT game_id, status, progress, downloaded_bytes, total_bytes, paused_at, partial_path, parent_id, language
+        FROM downloads ORDER BY game_id
+      `).all() as any[];
+    },
+
+    removeDownload(gameId: number): void {
+      const db = getDb();
+      db.prepare('DELETE FROM downloads WHERE game_id = ?').run(gameId);
+    },
   };
 }
 
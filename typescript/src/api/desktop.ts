@@ -0,0 +1,70 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import * as os from 'os';
+import { Game } from './game';
+
+export function getApplicationsDir(): string {
+  const dataDir = process.env.XDG_DATA_HOME || path.join(os.homedir(), '.local', 'share');
+  return path.join(dataDir, 'applications');
+}
+
+export function desktopEntryPath(gameId: number): string {
+  return path.join(getApplicationsDir(), `galaxi-${gameId}.desktop`);
+}
+
+/**
+ * The command that starts Galaxi itself: the compiled backend, or the runtime
+ * plus the server script when running from source.
+ */
+export function galaxiCommand(): string[] {
+  const script = process.argv[1];
+  if (script && script !== process.execPath && /\.(ts|js)$/.test(script) && fs.existsSync(script)) {
+    return [process.execPath, path.resolve(script)];
+  }
+  return [process.execPath];
+}
+
+/**
+ * Quote one Exec argument as the Desktop Entry spec asks: arguments with
+ * reserved characters are double quoted with ", `, $ and \ backslash-escaped,
+ * and % is doubled so it isn't taken for a field code.
+ */
+function quoteExecArg(arg: string): string {
+  let quoted = arg.replace(/%/g, '%%');
+  if (/[\s"'\\><~|&;$*?#()`]/.test(quoted)) {
+    quoted = `"${quoted.replace(/(["`$\\])/g, '\\$1')}"`;
+  }
+  // The Exec value is itself a string value, so backslashes are escaped again
+  return quoted.replace(/\\/g, '\\\\');
+}
+
+function escapeValue(value: string): string {
+  return value.replace(/\\/g, '\\\\').replace(/\r?\n/g, ' ');
+}
+
+export function buildDesktopEntry(game: Game, icon: string): string {
+  const exec = [...galaxiCommand(), '--launch', String(game.id)].map(quoteExecArg).join(' ');
+  return [
+    '[Desktop Entry]',
+    'Type=Application',
+    `Name=${escapeValue(game.name)}`,
+    'Comment=Play this game with Galaxi',
+    `Exec=${exec}`,
+    `Path=${escapeValue(game.install_dir)}`,
+    `Icon=${escapeValue(icon)}`,
+    'Terminal=false',
+    'Categories=Game;',
+    '',
+  ].join('\n');
+}
+
+export function writeDesktopEntry(game: Game, icon: string): string {
+  const entryPath = desktopEntryPath(game.id);
+  fs.mkdirSync(path.dirname(entryPath), { recursive: true });
+  fs.writeFileSync(entryPath, buildDesktopEntry(game, icon), { mode: 0o755 });
+  return entryPath;
+}
+
+export function removeDesktopEntry(gameId: number): void {
+  fs.rmSync(desktopEntryPath(gameId), { force: true });
+}
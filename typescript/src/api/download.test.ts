@@ -0,0 +1,185 @@
+import { describe, expect, test } from 'bun:test';
+import * as fs from 'fs';
+import * as path from 'path';
+import * as crypto from 'crypto';
+import { Readable } from 'stream';
+import { AxiosInstance } from 'axios';
+import {
+  DownloadManager,
+  DownloadStatus,
+  calculateMd5,
+  checkDownloadedFile,
+  findInstallerFiles,
+  partialDownloadPath,
+  verifyChecksum,
+} from './download';
+import { downloadsDb } from './database';
+import { useTempDataDir } from './testing';
+
+const dataDir = useTempDataDir({ database: true });
+
+// A server that never answers; requests only end by being aborted
+function stalledClient(): AxiosInstance {
+  return {
+    request: ({ signal }: { signal: AbortSignal }) =>
+      new Promise((_, reject) => {
+        signal.addEventListener('abort', () => reject(new Error('aborted')), { once: true });
+      }),
+  } as unknown as AxiosInstance;
+}
+
+// A server answering every request with an empty 200 response
+function emptyResponseClient(): AxiosInstance {
+  return {
+    request: async () => ({ status: 200, headers: { 'content-length': '0' }, data: Readable.from([]) }),
+  } as unknown as AxiosInstance;
+}
+
+describe('checkDownloadedFile', () => {
+  test('reports empty, truncated and implausibly small installers', () => {
+    const file = path.join(dataDir.dir, 'setup.exe');
+    expect(checkDownloadedFile(file, 0, 'setup.exe')).toBe('Downloaded file is empty');
+    fs.writeFileSync(file, '');
+    expect(checkDownloadedFile(file, 0, 'setup.exe')).toBe('Downloaded file is empty');
+    fs.writeFileSync(file, Buffer.alloc(100));
+    expect(checkDownloadedFile(file, 0, 'setup.exe')).toBe('Downloaded installer is implausibly small (100 bytes)');
+    expect(checkDownloadedFile(file, 0, 'notes.txt')).toBeNull();
+    fs.writeFileSync(file, Buffer.alloc(4096));
+    expect(checkDownloadedFile(file, 8192, 'setup.exe')).toBe('Downloaded file is truncated (4096 of 8192 bytes)');
+    expect(checkDownloadedFile(file, 4096, 'setup.exe')).toBeNull();
+  });
+});
+
+describe('calculateMd5', () => {
+  test('hashes a file spanning several read chunks like a one-shot hash', async () => {
+    // Not a multiple of the chunk size, so the last read is a short one
+    const data = crypto.randomBytes(5 * 1024 * 1024 + 123);
+    const file = path.join(dataDir.dir, 'setup.bin');
+    fs.writeFileSync(file, data);
+
+    const expected = crypto.createHash('md5').update(data).digest('hex');
+    expect(await calculateMd5(file)).toBe(expected);
+    expect(await verifyChecksum(file, expected.toUpperCase())).toBe(true);
+  });
+
+  test('hashes an empty file', async () => {
+    const file = path.join(dataDir.dir, 'empty');
+    fs.writeFileSync(file, '');
+    expect(await calculateMd5(file)).toBe('d41d8cd98f00b204e9800998ecf8427e');
+  });
+});
+
+describe('findInstallerFiles', () => {
+  test("an installer's parts are found without another game's partial files", () => {
+    const downloadsDir = path.join(dataDir.dir, '.downloads');
+    fs.mkdirSync(downloadsDir);
+    const names = ['setup_one.exe', 'setup_one-1.bin', 'setup_one-2.bin', 'setup_two.exe.part', 'setup_two-1.bin.part'];
+    for (const name of names) {
+      fs.writeFileSync(path.join(downloadsDir, name), name);
+    }
+
+    const files = findInstallerFiles(downloadsDir, path.join(downloadsDir, 'setup_one.exe'));
+    expect(files.map(file => path.basename(file))).toEqual(['setup_one.exe', 'setup_one-1.bin', 'setup_one-2.bin']);
+  });
+});
+
+describe('DownloadManager', () => {
+  test('an interrupted download resumes after a restart while a paused one stays paused', async () => {
+    const manager = new DownloadManager(stalledClient());
+    const interrupted = manager
+      .downloadFile({ id: 1, name: 'One' }, 'https://example.com/one.sh', path.join(dataDir.dir, 'one.sh'))
+      .catch(() => {});
+    const paused = manager
+      .downloadFile({ id: 2, name: 'Two' }, 'https://example.com/two.sh', path.join(dataDir.dir, 'two.sh'))
+      .catch(() => {});
+
+    manager.pauseDownload(2);
+    await paused;
+    await manager.stopAll();
+    await interrupted;
+
+    const stored = downloadsDb().getAllDownloads();
+    expect(stored.map(row => [row.game_id, row.status])).toEqual([
+      [1, DownloadStatus.Downloading],
+      [2, DownloadStatus.Paused],
+    ]);
+
+    const restarted = new DownloadManager(stalledClient());
+    restarted.restore(stored);
+    expect(restarted.getProgress(1)?.status).toBe(DownloadStatus.Pending);
+    expect(restarted.getProgress(2)?.status).toBe(DownloadStatus.Paused);
+  });
+
+  test('a DLC download keeps its game and language across a restart', async () => {
+    const manager = new DownloadManager(stalledClient());
+    const download = manager
+      .downloadFile(
+        { id: 20, name: 'Some DLC', parentId: 2, language: 'de' },
+        'https://example.com/dlc.sh',
+        path.join(dataDir.dir, 'dlc.sh')
+      )
+      .catch(() => {});
+    await manager.stopAll();
+    await download;
+
+    const stored = downloadsDb().getAllDownloads();
+    expect(stored.map(row => [row.game_id, row.parent_id, row.language])).toEqual([[20, 2, 'de']]);
+
+    const restarted = new DownloadManager(stalledClient());
+    restarted.restore(stored);
+    expect(restarted.getTarget(20)).toMatchObject({ parentId: 2, language: 'de' });
+  });
+
+  test('an empty response fails the download without leaving a file behind', async () => {
+    const manager = new DownloadManager(emptyResponseClient());
+    const destination = path.join(dataDir.dir, 'setup.exe');
+    await expect(
+      manager.downloadFile({ id: 3, name: 'Three' }, 'https://example.com/setup.exe', destination)
+    ).rejects.toThrow('Downloaded file is empty');
+
+    expect(manager.getProgress(3)?.status).toBe(DownloadStatus.Failed);
+    expect(fs.existsSync(destination)).toBe(false);
+    expect(fs.existsSync(partialDownloadPath(destination))).toBe(false);
+  });
+
+  test('chunks failing the checksum manifest are downloaded again', async () => {
+    const data = crypto.randomBytes(3000);
+    const md5 = (bytes: Buffer) => crypto.createHash('md5').update(bytes).digest('hex');
+    const chunks = [0, 1000, 2000].map((from, id) => ({
+      id,
+      from,
+      to: from + 999,
+      md5: md5(data.subarray(from, from + 1000)),
+    }));
+    // The full response has one flipped byte; ranges are served correctly
+    const corrupted = Buffer.from(data);
+    corrupted[1500] ^= 0xff;
+    const ranges: string[] = [];
+    const client = {
+      request: async ({ headers }: { headers: Record<string, string> }) => {
+        const range = /^bytes=(\d+)-(\d+)$/.exec(headers.Range ?? '');
+        if (!range) {
+          return { status: 200, headers: { 'content-length': String(corrupted.length) }, data: Readable.from([corrupted]) };
+        }
+        ranges.push(headers.Range);
+        const [start, end] = [parseInt(range[1], 10), parseInt(range[2], 10)];
+        return {
+          status: 206,
+          headers: { 'content-length': String(end - start + 1), 'content-range': `bytes ${start}-${end}/${data.length}` },
+          data: Readable.from([data.subarray(start, end + 1)]),
+        };
+      },
+    } as unknown as AxiosInstance;
+
+    const manager = new DownloadManager(client);
+    const destination = path.join(dataDir.dir, 'game.tar.gz');
+    await manager.downloadFile({ id: 4, name: 'Four' }, 'https://example.com/game.tar.gz', destination, {
+      expectedMd5: md5(data),
+      expectedChunks: chunks,
+    });
+
+    expect(ranges).toEqual(['bytes=1000-1999']);
+    expect(fs.readFileSync(destination)).toEqual(data);
+    expect(manager.getProgress(4)?.status).toBe(DownloadStatus.Completed);
+  });
+});
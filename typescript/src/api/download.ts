@@ -1,15 +1,27 @@
 import * as fs from 'fs';
 import * as path from 'path';
-import axios from 'axios';
+import * as crypto from 'crypto';
+import { pipeline } from 'stream/promises';
+import axios, { AxiosInstance } from 'axios';
 import { GalaxiError, GalaxiErrorType } from './error';
 import { DownloadProgressDto } from './dto';
-import { Game } from './game';
+import {
+  Config,
+  DEFAULT_DOWNLOAD_RETRIES,
+  DEFAULT_DOWNLOAD_SEGMENTS,
+  DEFAULT_DOWNLOAD_THREAD_COUNT,
+  MINIMUM_RESUME_SIZE,
+} from './config';
+import { downloadsDb, StoredDownload } from './database';
+import { ChunkMd5 } from './gog_api';
 
 export enum DownloadStatus {
+  Pending = 'Pending',
   Downloading = 'Downloading',
   Paused = 'Paused',
   Completed = 'Completed',
   Failed = 'Failed',
+  Cancelled = 'Cancelled',
 }
 
 export interface DownloadProgress {
@@ -18,102 +30,1104 @@ export interface DownloadProgress {
   downloaded: number;
   total: number;
   status: DownloadStatus;
+  speed_bytes_per_sec: number;
+  // Set while a failed transfer is being retried
+  retry_attempt?: number;
+  max_retries?: number;
+  // 1-based position while waiting in the download queue
+  queue_position?: number;
+  // 1-based index of the installer part being downloaded, out of total_files
+  current_file_index: number;
+  total_files: number;
+  error?: string;
+}
+
+/**
+ * Where a file sits within a multi-part installer. Progress for all parts is
+ * reported under the game's single entry, with bytes accumulated across parts.
+ */
+export interface DownloadPart {
+  // 0-based index of this file
+  index: number;
+  count: number;
+  // Bytes of the parts before this one
+  offset: number;
+  // Size of all parts together, 0 when unknown
+  totalBytes: number;
+}
+
+export interface DownloadFileOptions {
+  onProgress?: (progress: DownloadProgress) => void;
+  expectedMd5?: string;
+  // Chunk MD5s from GOG's checksum manifest; chunks failing them are
+  // downloaded again when the whole file doesn't match expectedMd5
+  expectedChunks?: ChunkMd5[];
+  part?: DownloadPart;
+}
+
+// Window over which the download speed is averaged
+const SPEED_WINDOW_MS = 5000;
+
+/**
+ * Rolling average of transfer speed over the last few seconds.
+ * Each transfer gets its own meter, so time spent paused isn't counted.
+ */
+class SpeedMeter {
+  private samples: { time: number; bytes: number }[] = [];
+
+  record(totalBytes: number): number {
+    const now = Date.now();
+    this.samples.push({ time: now, bytes: totalBytes });
+    // Keep one sample older than the window as the baseline
+    while (this.samples.length > 2 && now - this.samples[1].time > SPEED_WINDOW_MS) {
+      this.samples.shift();
+    }
+    const first = this.samples[0];
+    const elapsedMs = now - first.time;
+    if (elapsedMs <= 0) {
+      return 0;
+    }
+    return Math.round(((totalBytes - first.bytes) * 1000) / elapsedMs);
+  }
+}
+
+// Installers smaller than this are certainly not real GOG installers
+const MINIMUM_INSTALLER_SIZE = 1024;
+
+/**
+ * Check a finished download for an empty or truncated body.
+ * Returns a description of the problem, or null when the file looks complete.
+ */
+export function checkDownloadedFile(filePath: string, expectedSize: number, fileName: string = filePath): string | null {
+  const size = fs.existsSync(filePath) ? fs.statSync(filePath).size : 0;
+  if (size === 0) {
+    return 'Downloaded file is empty';
+  }
+  if (expectedSize > 0 && size < expectedSize) {
+    return `Downloaded file is truncated (${size} of ${expectedSize} bytes)`;
+  }
+  const lower = fileName.toLowerCase();
+  if ((lower.endsWith('.exe') || lower.endsWith('.sh') || lower.endsWith('.bin')) && size < MINIMUM_INSTALLER_SIZE) {
+    return `Downloaded installer is implausibly small (${size} bytes)`;
+  }
+  return null;
+}
+
+// Read size used when hashing; memory use stays constant regardless of file size
+const HASH_CHUNK_SIZE = 1024 * 1024;
+
+/**
+ * Compute the MD5 of a file, or of bytes start..end (inclusive) of it, as a
+ * lowercase hex string
+ */
+export function calculateMd5(filePath: string, start?: number, end?: number): Promise<string> {
+  return new Promise((resolve, reject) => {
+    const hash = crypto.createHash('md5');
+    const stream = fs.createReadStream(filePath, { highWaterMark: HASH_CHUNK_SIZE, start, end });
+    stream.on('data', chunk => hash.update(chunk));
+    stream.on('error', reject);
+    stream.on('end', () => resolve(hash.digest('hex')));
+  });
+}
+
+export async function verifyChecksum(filePath: string, expectedMd5: string): Promise<boolean> {
+  return (await calculateMd5(filePath)) === expectedMd5.toLowerCase();
+}
+
+/**
+ * Chunks of the file that don't match their MD5 from GOG's checksum manifest
+ */
+export async function findCorruptChunks(filePath: string, chunks: ChunkMd5[]): Promise<ChunkMd5[]> {
+  const corrupt: ChunkMd5[] = [];
+  for (const chunk of chunks) {
+    if ((await calculateMd5(filePath, chunk.from, chunk.to)) !== chunk.md5.toLowerCase()) {
+      corrupt.push(chunk);
+    }
+  }
+  return corrupt;
+}
+
+// Suffix for files still being downloaded; renamed away once complete
+const PARTIAL_SUFFIX = '.part';
+
+export function partialDownloadPath(destination: string): string {
+  return destination + PARTIAL_SUFFIX;
+}
+
+/**
+ * Installer files of one game in the shared downloads directory: the
+ * installer and its GOG-style "<name>-N.bin" parts. Other games' files,
+ * including their in-progress .part files, never match.
+ */
+export function findInstallerFiles(downloadsDir: string, installerPath: string): string[] {
+  const stem = path.basename(installerPath).replace(/\.[^.]+$/, '');
+  const parts = fs.existsSync(downloadsDir)
+    ? fs.readdirSync(downloadsDir).filter(name => name.startsWith(`${stem}-`) && name.endsWith('.bin')).sort()
+    : [];
+  return [installerPath, ...parts.map(name => path.join(downloadsDir, name))];
+}
+
+// Segments smaller than this aren't worth a connection of their own
+const MIN_SEGMENT_SIZE = 8 * 1024 * 1024;
+
+/**
+ * Partial file of one segment of a segmented download, e.g. setup.exe.part2
+ */
+function segmentPath(partialPath: string, index: number): string {
+  return `${partialPath}${index}`;
+}
+
+/**
+ * Segment files left by an interrupted segmented download, in order
+ */
+function existingSegments(partialPath: string): string[] {
+  const dir = path.dirname(partialPath);
+  const prefix = path.basename(partialPath);
+  if (!fs.existsSync(dir)) {
+    return [];
+  }
+  const indexes = fs.readdirSync(dir)
+    .filter(name => name.startsWith(prefix) && /^\d+$/.test(name.slice(prefix.length)))
+    .map(name => parseInt(name.slice(prefix.length), 10))
+    .sort((a, b) => a - b);
+  return indexes.map(index => segmentPath(partialPath, index));
+}
+
+/**
+ * Delete a download's partial file and any segment files next to it
+ */
+export function removePartialFiles(partialPath: string): void {
+  for (const file of [partialPath, ...existingSegments(partialPath)]) {
+    fs.rmSync(file, { force: true });
+  }
+}
+
+/**
+ * Bytes a download has written so far, over its partial file and segments
+ */
+function partialSize(partialPath: string): number {
+  return [partialPath, ...existingSegments(partialPath)]
+    .filter(file => fs.existsSync(file))
+    .reduce((sum, file) => sum + fs.statSync(file).size, 0);
+}
+
+/**
+ * Flush a file and then its directory entry to disk, so after a crash a
+ * renamed file is either fully there or not there at all
+ */
+function syncToDisk(filePath: string): void {
+  for (const target of [filePath, path.dirname(filePath)]) {
+    const fd = fs.openSync(target, 'r');
+    try {
+      fs.fsyncSync(fd);
+    } finally {
+      fs.closeSync(fd);
+    }
+  }
+}
+
+/**
+ * Stream a response body into a file. A body that breaks off is flushed and
+ * reported as a StreamInterruptedError, so the attempt can be resumed.
+ */
+function writeResponse(response: any, filePath: string, append: boolean, onChunk: (bytes: number) => void): Promise<void> {
+  const writer = fs.createWriteStream(filePath, { flags: append ? 'a' : 'w' });
+  response.data.on('data', (chunk: Buffer) => onChunk(chunk.length));
+
+  return new Promise<void>((resolve, reject) => {
+    let streamError: Error | null = null;
+    writer.on('finish', () => (streamError ? reject(streamError) : resolve()));
+    writer.on('error', reject);
+    response.data.on('error', (err: Error) => {
+      // Flush what was received so the partial file can be resumed
+      streamError = new StreamInterruptedError(err.message);
+      response.data.unpipe(writer);
+      writer.end();
+    });
+    response.data.pipe(writer);
+  });
+}
+
+// Minimum time between writes of a running download's progress to the database
+const PERSIST_INTERVAL_MS = 2000;
+
+// Minimum time between progress events of a running download, see subscribe
+const NOTIFY_INTERVAL_MS = 250;
+
+// How long stopAll and cancelAll wait for aborted downloads to settle, e.g.
+// one stuck in a slow read, before giving up on them
+const STOP_TIMEOUT_MS = 5000;
+
+// Base delay before retrying a failed transfer; doubled on every attempt
+const RETRY_BASE_DELAY_MS = 1000;
+
+// Network error codes worth retrying
+const TRANSIENT_ERROR_CODES = new Set([
+  'ECONNRESET',
+  'ECONNABORTED',
+  'ETIMEDOUT',
+  'EPIPE',
+  'EAI_AGAIN',
+  'ENETUNREACH',
+  'ERR_NETWORK',
+]);
+
+// Raised when the response stream breaks off mid-transfer
+class StreamInterruptedError extends Error {}
+
+/**
+ * Whether a failed transfer is worth retrying. Server errors, timeouts and
+ * dropped connections are; client errors such as 404 are not.
+ */
+function isTransientError(error: any): boolean {
+  if (error instanceof StreamInterruptedError) {
+    return true;
+  }
+  const status = error?.response?.status;
+  if (status !== undefined) {
+    return status >= 500 || status === 408 || status === 429;
+  }
+  return TRANSIENT_ERROR_CODES.has(error?.code);
+}
+
+/**
+ * Whether a 416 answer to resuming at `startByte` means the partial file
+ * already holds the whole file: the full size the server reports in its
+ * Content-Range header matches what is on disk
+ */
+function isAlreadyComplete(error: any, startByte: number): boolean {
+  if (startByte === 0 || error?.response?.status !== 416) {
+    return false;
+  }
+  const match = /^bytes \*\/(\d+)$/.exec(error.response.headers?.['content-range'] ?? '');
+  return match !== null && parseInt(match[1], 10) === startByte;
+}
+
+function waitBeforeRetry(ms: number, signal: AbortSignal): Promise<void> {
+  return new Promise((resolve, reject) => {
+    const timer = setTimeout(resolve, ms);
+    signal.addEventListener('abort', () => {
+      clearTimeout(timer);
+      reject(new Error('Download aborted'));
+    }, { once: true });
+  });
+}
+
+/**
+ * What a download is tracked under: a game, or a DLC by its own product id
+ */
+export interface DownloadTarget {
+  id: number;
+  name: string;
+  // The game a DLC belongs to
+  parentId?: number;
+  // Language chosen for the download instead of the configured one
+  language?: string;
+}
+
+interface QueuedDownload {
+  gameId: number;
+  start: () => void;
+  drop: (reason: Error) => void;
 }
 
 export class DownloadManager {
   private downloads: Map<number, DownloadProgress> = new Map();
+  private abortControllers: Map<number, AbortController> = new Map();
+  // Partial file of each game's current part, removed when the download is cancelled
+  private partialPaths: Map<number, string> = new Map();
+  // What each download was started for, stored so it is resumed the same way
+  private targets: Map<number, DownloadTarget> = new Map();
+  // Games holding one of the max_parallel_game_downloads slots, and those waiting for one
+  private running: Set<number> = new Set();
+  private queue: QueuedDownload[] = [];
+  private lastPersisted: Map<number, number> = new Map();
+  private listeners: Set<(progress: DownloadProgress) => void> = new Set();
+  private lastNotified: Map<number, number> = new Map();
+  private stopped: boolean = false;
+  private client: AxiosInstance;
+  private config?: Config;
+
+  constructor(client?: AxiosInstance, config?: Config) {
+    this.client = client || axios.create();
+    this.config = config;
+  }
+
+  /**
+   * Use another config from now on, e.g. after restoring a database backup
+   */
+  setConfig(config: Config): void {
+    this.config = config;
+  }
+
+  /**
+   * Wait for a free download slot for the game. While waiting the game shows
+   * up as Pending with its queue position. Rejects if the game is cancelled
+   * or paused before its turn, or the manager shuts down.
+   * Every successful call must be paired with releaseSlot.
+   */
+  acquireSlot(game: DownloadTarget): Promise<void> {
+    if (this.stopped) {
+      return Promise.reject(new GalaxiError('Download manager is shut down', GalaxiErrorType.DownloadError));
+    }
+    this.targets.set(game.id, game);
+    if (this.queue.length === 0 && this.running.size < this.maxParallelDownloads()) {
+      this.running.add(game.id);
+      return Promise.resolve();
+    }
+
+    return new Promise((resolve, reject) => {
+      const progress: DownloadProgress = {
+        game_id: game.id,
+        file_name: game.name,
+        downloaded: 0,
+        total: 0,
+        status: DownloadStatus.Pending,
+        speed_bytes_per_sec: 0,
+        current_file_index: 0,
+        total_files: 0,
+      };
+      this.downloads.set(game.id, progress);
+      this.persist(progress);
+      this.queue.push({ gameId: game.id, start: resolve, drop: reject });
+      this.updateQueuePositions();
+    });
+  }
+
+  releaseSlot(gameId: number): void {
+    this.running.delete(gameId);
+    this.dispatchQueue();
+  }
+
+  /**
+   * Start queued downloads while slots are free. Call after the parallel
+   * download limit changes.
+   */
+  dispatchQueue(): void {
+    while (this.queue.length > 0 && this.running.size < this.maxParallelDownloads()) {
+      const next = this.queue.shift()!;
+      this.running.add(next.gameId);
+      const progress = this.downloads.get(next.gameId);
+      if (progress) {
+        progress.status = DownloadStatus.Downloading;
+        progress.queue_position = undefined;
+        this.persist(progress);
+      }
+      next.start();
+    }
+    this.updateQueuePositions();
+  }
+
+  /**
+   * Whether the game is downloading or waiting in the queue in this session
+   */
+  isActive(gameId: number): boolean {
+    return this.running.has(gameId) || this.queue.some(entry => entry.gameId === gameId);
+  }
+
+  /**
+   * Load downloads left over from a previous session so they show up before
+   * being resumed. Interrupted downloads come back as Pending.
+   */
+  restore(stored: StoredDownload[]): void {
+    for (const row of stored) {
+      if (this.downloads.has(row.game_id)) {
+        continue;
+      }
+      const status = row.status === DownloadStatus.Paused ? DownloadStatus.Paused : DownloadStatus.Pending;
+      this.downloads.set(row.game_id, {
+        game_id: row.game_id,
+        file_name: '',
+        downloaded: row.downloaded_bytes || 0,
+        total: row.total_bytes || 0,
+        status,
+        speed_bytes_per_sec: 0,
+        current_file_index: 0,
+        total_files: 0,
+      });
+      if (row.partial_path) {
+        this.partialPaths.set(row.game_id, row.partial_path);
+      }
+      this.targets.set(row.game_id, {
+        id: row.game_id,
+        name: '',
+        parentId: row.parent_id ?? undefined,
+        language: row.language ?? undefined,
+      });
+    }
+  }
+
+  /**
+   * What the download tracked under the id was started for, also for one
+   * restored from a previous session
+   */
+  getTarget(gameId: number): DownloadTarget | undefined {
+    return this.targets.get(gameId);
+  }
+
+  /**
+   * Delete partial files in the downloads directory that no known download
+   * will resume, e.g. left behind by a crash. Returns the removed paths.
+   */
+  cleanupStalePartials(downloadsDir: string): string[] {
+    if (!fs.existsSync(downloadsDir)) {
+      return [];
+    }
+    const kept = new Set(Array.from(this.partialPaths.values()).map(p => path.resolve(p)));
+    const removed: string[] = [];
+    for (const entry of fs.readdirSync(downloadsDir)) {
+      const match = /^(.*\.part)\d*$/.exec(entry);
+      if (!match || kept.has(path.resolve(downloadsDir, match[1]))) {
+        continue;
+      }
+      const file = path.join(downloadsDir, entry);
+      fs.rmSync(file, { force: true });
+      removed.push(file);
+    }
+    return removed;
+  }
+
+  /**
+   * Call the listener whenever a download's progress changes. Status changes
+   * are always reported, routine byte counts at most every NOTIFY_INTERVAL_MS
+   * per game. Returns a function that removes the listener.
+   */
+  subscribe(listener: (progress: DownloadProgress) => void): () => void {
+    this.listeners.add(listener);
+    return () => {
+      this.listeners.delete(listener);
+    };
+  }
+
+  private notify(progress: DownloadProgress, force: boolean): void {
+    if (this.listeners.size === 0) {
+      return;
+    }
+    const now = Date.now();
+    if (!force && now - (this.lastNotified.get(progress.game_id) || 0) < NOTIFY_INTERVAL_MS) {
+      return;
+    }
+    this.lastNotified.set(progress.game_id, now);
+    for (const listener of this.listeners) {
+      try {
+        listener({ ...progress });
+      } catch (error) {
+        console.warn('Download progress listener failed:', error);
+      }
+    }
+  }
+
+  /**
+   * Write a download's state to the downloads table; finished downloads are
+   * removed from it, and tell listeners about the change. Routine progress
+   * updates are throttled unless forced.
+   */
+  private persist(progress: DownloadProgress, force: boolean = true): void {
+    this.notify(progress, force);
+    const now = Date.now();
+    if (!force && now - (this.lastPersisted.get(progress.game_id) || 0) < PERSIST_INTERVAL_MS) {
+      return;
+    }
+    this.lastPersisted.set(progress.game_id, now);
+
+    try {
+      if (
+        progress.status === DownloadStatus.Completed ||
+        progress.status === DownloadStatus.Failed ||
+        progress.status === DownloadStatus.Cancelled
+      ) {
+        downloadsDb().removeDownload(progress.game_id);
+        this.lastPersisted.delete(progress.game_id);
+      } else {
+        const target = this.targets.get(progress.game_id);
+        downloadsDb().saveDownload(
+          progress.game_id,
+          progress.status,
+          progress.downloaded,
+          progress.total,
+          this.partialPaths.get(progress.game_id) ?? null,
+          target?.parentId ?? null,
+          target?.language ?? null
+        );
+      }
+    } catch (error) {
+      console.warn(`Failed to persist download state for game ${progress.game_id}:`, error);
+    }
+  }
+
+  /**
+   * Flush the state of every known download to the database
+   */
+  persistAll(): void {
+    for (const progress of this.downloads.values()) {
+      this.persist(progress);
+    }
+  }
+
+  private maxParallelDownloads(): number {
+    return this.config ? this.config.max_parallel_game_downloads : DEFAULT_DOWNLOAD_THREAD_COUNT;
+  }
+
+  private updateQueuePositions(): void {
+    this.queue.forEach((entry, index) => {
+      const progress = this.downloads.get(entry.gameId);
+      if (progress) {
+        progress.queue_position = index + 1;
+      }
+    });
+  }
+
+  /**
+   * Take a game out of the queue before it started. Returns false if it wasn't queued.
+   */
+  private dropFromQueue(gameId: number, reason: string): boolean {
+    const index = this.queue.findIndex(entry => entry.gameId === gameId);
+    if (index === -1) {
+      return false;
+    }
+    const [entry] = this.queue.splice(index, 1);
+    entry.drop(new GalaxiError(reason, GalaxiErrorType.DownloadError));
+    this.updateQueuePositions();
+    return true;
+  }
 
   async downloadFile(
-    game: Game,
+    game: DownloadTarget,
     url: string,
     destination: string,
-    onProgress?: (progress: DownloadProgress) => void
+    options: DownloadFileOptions = {}
   ): Promise<void> {
+    const { onProgress, expectedMd5, expectedChunks } = options;
+    const part: DownloadPart = options.part || { index: 0, count: 1, offset: 0, totalBytes: 0 };
     const fileName = path.basename(destination);
+    const partialPath = partialDownloadPath(destination);
     
     const progress: DownloadProgress = {
       game_id: game.id,
       file_name: fileName,
-      downloaded: 0,
-      total: 0,
+      downloaded: part.offset,
+      total: part.totalBytes,
       status: DownloadStatus.Downloading,
+      speed_bytes_per_sec: 0,
+      current_file_index: part.index + 1,
+      total_files: part.count,
     };
 
+    if (this.stopped) {
+      throw new GalaxiError('Download manager is shut down', GalaxiErrorType.DownloadError);
+    }
+
+    this.targets.set(game.id, game);
     this.downloads.set(game.id, progress);
+    this.partialPaths.set(game.id, partialPath);
+    this.persist(progress);
+
+    const controller = new AbortController();
+    this.abortControllers.set(game.id, controller);
+
+    const maxRetries = this.config ? this.config.max_download_retries : DEFAULT_DOWNLOAD_RETRIES;
 
     try {
-      // Check if file exists and get its size for resume
-      let startByte = 0;
-      if (fs.existsSync(destination)) {
-        startByte = fs.statSync(destination).size;
-        progress.downloaded = startByte;
-        this.downloads.set(game.id, { ...progress });
+      // Each retry resumes from whatever the previous attempt wrote
+      let fileSize = 0;
+      for (let attempt = 0; ; attempt++) {
+        try {
+          fileSize = await this.transfer(url, partialPath, progress, part, controller.signal, onProgress);
+          break;
+        } catch (error: any) {
+          if (controller.signal.aborted || attempt >= maxRetries || !isTransientError(error)) {
+            throw error;
+          }
+          progress.retry_attempt = attempt + 1;
+          progress.max_retries = maxRetries;
+          progress.speed_bytes_per_sec = 0;
+          this.persist(progress);
+          if (onProgress) {
+            onProgress(progress);
+          }
+          const delay = RETRY_BASE_DELAY_MS * 2 ** attempt;
+          console.warn(
+            `Download of ${fileName} failed (${error.message}), retrying in ${delay}ms (${attempt + 1}/${maxRetries})`
+          );
+          await waitBeforeRetry(delay, controller.signal);
+        }
+      }
+      progress.retry_attempt = undefined;
+      progress.max_retries = undefined;
+
+      // Check the partial file before it gets its final name, so a bogus or
+      // corrupt file never sits where the installer would pick it up; a
+      // retry then downloads it from scratch
+      const problem = checkDownloadedFile(partialPath, fileSize, fileName);
+      if (problem) {
+        fs.rmSync(partialPath, { force: true });
+        throw new Error(problem);
+      }
+      if (
+        expectedMd5 &&
+        !(await verifyChecksum(partialPath, expectedMd5)) &&
+        !(await this.refetchCorruptChunks(url, partialPath, expectedChunks ?? [], expectedMd5, controller.signal))
+      ) {
+        fs.rmSync(partialPath, { force: true });
+        throw new Error(`checksum mismatch for ${fileName}`);
+      }
+
+      // The rename is atomic; syncing first means it can't expose a file
+      // whose data didn't reach the disk
+      syncToDisk(partialPath);
+      fs.renameSync(partialPath, destination);
+      syncToDisk(destination);
+
+      // Earlier parts of a multi-part installer leave the game downloading
+      if (part.index === part.count - 1) {
+        progress.status = DownloadStatus.Completed;
+        this.partialPaths.delete(game.id);
+      }
+      progress.speed_bytes_per_sec = 0;
+      this.downloads.set(game.id, progress);
+      this.persist(progress);
+      if (onProgress) {
+        onProgress(progress);
+      }
+    } catch (error: any) {
+      // A paused download stops here and keeps its partial file for resuming,
+      // unless so little was written that starting over costs nothing
+      if (controller.signal.aborted && progress.status === DownloadStatus.Paused) {
+        if (partialSize(partialPath) < MINIMUM_RESUME_SIZE) {
+          removePartialFiles(partialPath);
+        }
+        progress.speed_bytes_per_sec = 0;
+        this.persist(progress);
+        if (onProgress) {
+          onProgress(progress);
+        }
+        return;
+      }
+      // A cancelled download throws its partial file away
+      if (controller.signal.aborted && progress.status === DownloadStatus.Cancelled) {
+        removePartialFiles(partialPath);
+        this.partialPaths.delete(game.id);
+        progress.speed_bytes_per_sec = 0;
+        if (onProgress) {
+          onProgress(progress);
+        }
+        return;
+      }
+      // An abort during shutdown leaves the partial file on disk and keeps the
+      // current status, so an interrupted download is resumed on next start
+      // while one the user paused stays paused
+      if (!controller.signal.aborted) {
+        progress.status = DownloadStatus.Failed;
+        progress.error = error.message;
+        removePartialFiles(partialPath);
+        this.partialPaths.delete(game.id);
+      }
+      progress.speed_bytes_per_sec = 0;
+      this.downloads.set(game.id, progress);
+      this.persist(progress);
+      if (onProgress) {
+        onProgress(progress);
+      }
+      throw new GalaxiError(
+        `Download failed: ${error.message}`,
+        GalaxiErrorType.DownloadError
+      );
+    } finally {
+      if (this.abortControllers.get(game.id) === controller) {
+        this.abortControllers.delete(game.id);
       }
+    }
+  }
 
-      const response = await axios({
+  /**
+   * Download the chunks of the file that fail their manifest MD5 again, over
+   * the bytes already written. Returns whether the whole file matches then;
+   * false straight away when no chunk explains the mismatch or the server
+   * doesn't honor range requests.
+   */
+  private async refetchCorruptChunks(
+    url: string,
+    filePath: string,
+    chunks: ChunkMd5[],
+    expectedMd5: string,
+    signal: AbortSignal
+  ): Promise<boolean> {
+    const corrupt = await findCorruptChunks(filePath, chunks);
+    if (corrupt.length === 0) {
+      return false;
+    }
+    console.warn(`Downloading ${corrupt.length} corrupt chunks of ${path.basename(filePath)} again`);
+    for (const chunk of corrupt) {
+      const response = await this.client.request({
         method: 'GET',
         url,
         responseType: 'stream',
-        headers: startByte > 0 ? { Range: `bytes=${startByte}-` } : {},
+        headers: { Range: `bytes=${chunk.from}-${chunk.to}` },
+        signal,
       });
+      if (response.status !== 206) {
+        response.data.destroy();
+        return false;
+      }
+      await pipeline(response.data, fs.createWriteStream(filePath, { flags: 'r+', start: chunk.from }));
+    }
+    return verifyChecksum(filePath, expectedMd5);
+  }
+
+  /**
+   * Run one transfer attempt. With download_segments above 1 and a server
+   * that honors range requests, the file is fetched in that many segments
+   * side by side; otherwise, or to resume a single-stream partial file, over
+   * one connection. Returns the expected size of the file.
+   */
+  private async transfer(
+    url: string,
+    partialPath: string,
+    progress: DownloadProgress,
+    part: DownloadPart,
+    signal: AbortSignal,
+    onProgress?: (progress: DownloadProgress) => void
+  ): Promise<number> {
+    // An interrupted segmented download keeps its layout even if the setting changed
+    const leftover = existingSegments(partialPath).length;
+    const segments = leftover > 0 ? leftover : this.segmentCount();
+    if (fs.existsSync(partialPath)) {
+      // Segments that were already being joined; the partial file is resumed
+      for (const file of existingSegments(partialPath)) {
+        fs.rmSync(file, { force: true });
+      }
+    } else if (segments > 1) {
+      const fileSize = await this.probeFileSize(url, signal);
+      if (fileSize !== null && (leftover > 0 || fileSize >= segments * MIN_SEGMENT_SIZE)) {
+        return this.transferSegmented(url, partialPath, fileSize, segments, progress, part, signal, onProgress);
+      }
+      // No range support: start over on a single stream
+      removePartialFiles(partialPath);
+    }
+    return this.transferSingle(url, partialPath, progress, part, signal, onProgress);
+  }
 
-      progress.total = parseInt(response.headers['content-length'] || '0') + startByte;
-      this.downloads.set(game.id, { ...progress });
+  private segmentCount(): number {
+    return this.config ? this.config.download_segments : DEFAULT_DOWNLOAD_SEGMENTS;
+  }
 
-      const writer = fs.createWriteStream(destination, { flags: startByte > 0 ? 'a' : 'w' });
+  /**
+   * Size of the file behind the URL if the server honors range requests
+   * (answers a one-byte range with 206), null otherwise
+   */
+  private async probeFileSize(url: string, signal: AbortSignal): Promise<number | null> {
+    const response = await this.client.request({
+      method: 'GET',
+      url,
+      responseType: 'stream',
+      headers: { Range: 'bytes=0-0' },
+      signal,
+    });
+    response.data.destroy();
+    if (response.status !== 206) {
+      return null;
+    }
+    const match = /\/(\d+)$/.exec(String(response.headers['content-range'] || ''));
+    return match ? parseInt(match[1], 10) : null;
+  }
 
-      response.data.on('data', (chunk: Buffer) => {
-        progress.downloaded += chunk.length;
-        // Update the shared downloads Map so getProgress can read it
-        this.downloads.set(game.id, { ...progress });
-        if (onProgress) {
-          onProgress(progress);
-        }
-      });
+  /**
+   * Download the file as `segments` byte ranges into their own partial files
+   * at the same time, then join them into the partial file. Each segment
+   * resumes from what an earlier attempt wrote. If one segment fails the
+   * others are stopped before the error is passed on.
+   */
+  private async transferSegmented(
+    url: string,
+    partialPath: string,
+    fileSize: number,
+    segments: number,
+    progress: DownloadProgress,
+    part: DownloadPart,
+    signal: AbortSignal,
+    onProgress?: (progress: DownloadProgress) => void
+  ): Promise<number> {
+    const speedMeter = new SpeedMeter();
+    const ranges = Array.from({ length: segments }, (_, i) => ({
+      path: segmentPath(partialPath, i),
+      start: Math.floor((i * fileSize) / segments),
+      end: Math.floor(((i + 1) * fileSize) / segments) - 1,
+    }));
 
-      await new Promise<void>((resolve, reject) => {
-        writer.on('finish', resolve);
-        writer.on('error', reject);
-        response.data.pipe(writer);
-      });
+    // Create every segment file up front, so a resumed download finds them
+    // all and splits the file the same way
+    for (const range of ranges) {
+      if (!fs.existsSync(range.path)) {
+        fs.writeFileSync(range.path, '');
+      }
+    }
+    const written = ranges.reduce((sum, range) => sum + fs.statSync(range.path).size, 0);
+    progress.downloaded = part.offset + written;
+    progress.total = part.totalBytes > 0 ? part.totalBytes : part.offset + fileSize;
+    this.downloads.set(progress.game_id, progress);
 
-      progress.status = DownloadStatus.Completed;
-      this.downloads.set(game.id, { ...progress });
+    const onChunk = (bytes: number) => {
+      progress.downloaded += bytes;
+      progress.speed_bytes_per_sec = speedMeter.record(progress.downloaded);
+      this.downloads.set(progress.game_id, progress);
+      this.persist(progress, false);
       if (onProgress) {
         onProgress(progress);
       }
+    };
+
+    // Stop the other segments when one fails, or when the download is aborted
+    const segmentController = new AbortController();
+    const abortSegments = () => segmentController.abort();
+    signal.addEventListener('abort', abortSegments, { once: true });
+    let firstError: unknown;
+    try {
+      await Promise.all(ranges.map(async (range) => {
+        try {
+          await this.fetchSegment(url, range.path, range.start, range.end, segmentController.signal, onChunk);
+        } catch (error) {
+          firstError ??= error;
+          segmentController.abort();
+        }
+      }));
+    } finally {
+      signal.removeEventListener('abort', abortSegments);
+    }
+    if (firstError !== undefined) {
+      throw firstError;
+    }
+
+    for (const [index, range] of ranges.entries()) {
+      await pipeline(
+        fs.createReadStream(range.path),
+        fs.createWriteStream(partialPath, { flags: index === 0 ? 'w' : 'a' })
+      );
+    }
+    for (const range of ranges) {
+      fs.rmSync(range.path, { force: true });
+    }
+    return fileSize;
+  }
+
+  /**
+   * Fetch bytes start..end (inclusive) into a segment file, continuing after
+   * what it already holds
+   */
+  private async fetchSegment(
+    url: string,
+    filePath: string,
+    start: number,
+    end: number,
+    signal: AbortSignal,
+    onChunk: (bytes: number) => void
+  ): Promise<void> {
+    const have = fs.existsSync(filePath) ? fs.statSync(filePath).size : 0;
+    if (start + have > end) {
+      return;
+    }
+    const response = await this.client.request({
+      method: 'GET',
+      url,
+      responseType: 'stream',
+      headers: { Range: `bytes=${start + have}-${end}` },
+      signal,
+    });
+    if (response.status !== 206) {
+      response.data.destroy();
+      throw new Error('The server stopped honoring range requests');
+    }
+    await writeResponse(response, filePath, have > 0, onChunk);
+    if (fs.statSync(filePath).size !== end - start + 1) {
+      throw new StreamInterruptedError(`Segment of ${path.basename(filePath)} is incomplete`);
+    }
+  }
+
+  /**
+   * Run a single transfer attempt into the partial file, resuming from its
+   * current size when the server honors range requests.
+   * Returns the expected size of the file.
+   */
+  private async transferSingle(
+    url: string,
+    partialPath: string,
+    progress: DownloadProgress,
+    part: DownloadPart,
+    signal: AbortSignal,
+    onProgress?: (progress: DownloadProgress) => void
+  ): Promise<number> {
+    const speedMeter = new SpeedMeter();
+
+    let startByte = 0;
+    if (fs.existsSync(partialPath)) {
+      startByte = fs.statSync(partialPath).size;
+    }
+    progress.downloaded = part.offset + startByte;
+
+    let response;
+    try {
+      response = await this.client.request({
+        method: 'GET',
+        url,
+        responseType: 'stream',
+        headers: startByte > 0 ? { Range: `bytes=${startByte}-` } : {},
+        signal,
+      });
     } catch (error: any) {
-      progress.status = DownloadStatus.Failed;
-      this.downloads.set(game.id, { ...progress });
+      // Nothing left to fetch; the caller verifies and renames the file
+      if (isAlreadyComplete(error, startByte)) {
+        error.response.data?.destroy?.();
+        progress.total = part.totalBytes > 0 ? part.totalBytes : part.offset + startByte;
+        this.downloads.set(progress.game_id, progress);
+        return startByte;
+      }
+      throw error;
+    }
+
+    // The server ignored the range request and sent the whole file
+    if (startByte > 0 && response.status !== 206) {
+      startByte = 0;
+      progress.downloaded = part.offset;
+    }
+
+    const fileSize = parseInt(response.headers['content-length'] || '0') + startByte;
+    progress.total = part.totalBytes > 0 ? part.totalBytes : part.offset + fileSize;
+    this.downloads.set(progress.game_id, progress);
+
+    await writeResponse(response, partialPath, startByte > 0, (bytes) => {
+      progress.downloaded += bytes;
+      progress.speed_bytes_per_sec = speedMeter.record(progress.downloaded);
+      // Update the shared downloads Map so getProgress can read it
+      this.downloads.set(progress.game_id, progress);
+      this.persist(progress, false);
       if (onProgress) {
         onProgress(progress);
       }
-      throw new GalaxiError(
-        `Download failed: ${error.message}`,
-        GalaxiErrorType.DownloadError
-      );
-    }
+    });
+
+    return fileSize;
   }
 
   getProgress(gameId: number): DownloadProgress | undefined {
     return this.downloads.get(gameId);
   }
 
+  /**
+   * Mark a multi-part download finished when its last part was already on
+   * disk, so no downloadFile call got to complete it
+   */
+  completeDownload(gameId: number): void {
+    const progress = this.downloads.get(gameId);
+    if (!progress || progress.status !== DownloadStatus.Downloading) {
+      return;
+    }
+    progress.status = DownloadStatus.Completed;
+    progress.downloaded = progress.total;
+    progress.speed_bytes_per_sec = 0;
+    this.partialPaths.delete(gameId);
+    this.persist(progress);
+  }
+
+  getAllProgress(): DownloadProgress[] {
+    return Array.from(this.downloads.values());
+  }
+
   pauseDownload(gameId: number): void {
     const progress = this.downloads.get(gameId);
     if (progress) {
       progress.status = DownloadStatus.Paused;
+      progress.speed_bytes_per_sec = 0;
+      progress.queue_position = undefined;
+      this.persist(progress);
     }
+    this.dropFromQueue(gameId, 'Download paused');
+    this.abortControllers.get(gameId)?.abort();
   }
 
   resumeDownload(gameId: number): void {
     const progress = this.downloads.get(gameId);
     if (progress) {
       progress.status = DownloadStatus.Downloading;
+      this.persist(progress);
     }
   }
 
+  /**
+   * Cancel a queued, running or paused download. A running transfer is aborted
+   * and deletes its partial file; a paused one has its partial file deleted here.
+   */
   cancelDownload(gameId: number): void {
-    this.downloads.delete(gameId);
+    const progress = this.downloads.get(gameId);
+    if (progress) {
+      progress.status = DownloadStatus.Cancelled;
+      progress.speed_bytes_per_sec = 0;
+      progress.queue_position = undefined;
+      this.persist(progress);
+    }
+    this.dropFromQueue(gameId, 'Download cancelled');
+
+    const controller = this.abortControllers.get(gameId);
+    if (controller) {
+      controller.abort();
+      return;
+    }
+    const partialPath = this.partialPaths.get(gameId);
+    if (partialPath) {
+      removePartialFiles(partialPath);
+      this.partialPaths.delete(gameId);
+    }
+  }
+
+  /**
+   * Cancel every download that hasn't finished yet and wait for running
+   * transfers to clean up, giving up on ones stuck after the timeout like
+   * stopAll. Returns the ids of the cancelled games.
+   */
+  async cancelAll(timeoutMs: number = STOP_TIMEOUT_MS): Promise<number[]> {
+    const cancelled = this.getAllProgress()
+      .filter(progress =>
+        progress.status === DownloadStatus.Pending ||
+        progress.status === DownloadStatus.Downloading ||
+        progress.status === DownloadStatus.Paused
+      )
+      .map(progress => progress.game_id);
+
+    for (const gameId of cancelled) {
+      this.cancelDownload(gameId);
+    }
+    const deadline = Date.now() + timeoutMs;
+    while (cancelled.some(gameId => this.abortControllers.has(gameId))) {
+      if (Date.now() >= deadline) {
+        const running = cancelled.filter(gameId => this.abortControllers.has(gameId));
+        console.warn(`Downloads still running after cancel timeout: ${running.join(', ')}`);
+        break;
+      }
+      await new Promise(resolve => setTimeout(resolve, 50));
+    }
+    return cancelled;
+  }
+
+  /**
+   * Stop accepting new downloads and abort every in-flight transfer.
+   * Resolves once all running downloads have settled, or after the timeout
+   * with the ones that haven't left behind. Their partial files stay on disk
+   * and persistAll still records their progress.
+   */
+  async stopAll(timeoutMs: number = STOP_TIMEOUT_MS): Promise<void> {
+    this.stopped = true;
+    // Queued downloads keep their Pending entry so they're persisted for next start
+    for (const entry of this.queue.splice(0)) {
+      entry.drop(new GalaxiError('Download manager is shut down', GalaxiErrorType.DownloadError));
+    }
+    for (const controller of this.abortControllers.values()) {
+      controller.abort();
+    }
+    const deadline = Date.now() + timeoutMs;
+    while (this.abortControllers.size > 0) {
+      if (Date.now() >= deadline) {
+        console.warn(`Downloads still running after shutdown timeout: ${Array.from(this.abortControllers.keys()).join(', ')}`);
+        return;
+      }
+      await new Promise(resolve => setTimeout(resolve, 50));
+    }
   }
 }
@@ -9,6 +9,45 @@ export interface GameDto {
   platform: string;
   category: string;
   dlcs: DlcDto[];
+  hidden?: boolean;
+  favorite?: boolean;
+  tags?: string[];
+}
+
+export interface InstallResultDto extends GameDto {
+  installed_version?: string;
+  installed_language?: string;
+}
+
+export interface InstallerCachePruneDto {
+  removed: string[]; // paths of the deleted installer files
+  freed_bytes: number;
+}
+
+export interface CachedInstallerDto {
+  name: string; // file name inside the downloads directory
+  size: number;
+  game_id?: number; // unknown for files no download or manifest entry claims
+  partial: boolean; // an unfinished .part file
+}
+
+export interface LibraryPageDto {
+  games: GameDto[];
+  page: number; // 1-based
+  total_pages: number;
+  total_count: number;
+}
+
+export interface UpdateInfoDto {
+  game_id: number;
+  name: string;
+  installed_version: string;
+  available_version: string;
+}
+
+export interface InstalledVersionDto {
+  version?: string;
+  language?: string;
 }
 
 export interface DlcDto {
@@ -16,6 +55,9 @@ export interface DlcDto {
   name: string;
   title: string;
   image_url: string;
+  // Only set where platform availability is known
+  installable?: boolean;
+  installed?: boolean;
 }
 
 export interface AccountDto {
@@ -25,6 +67,14 @@ export interface AccountDto {
   avatar_url?: string;
 }
 
+export interface SwitchResultDto {
+  success: boolean;
+  // The stored refresh token was revoked, the account has to log in again
+  needs_reauth: boolean;
+  // GOG couldn't be reached; the library is served from the database
+  offline?: boolean;
+}
+
 export interface UserDataDto {
   user_id: string;
   username: string;
@@ -44,14 +94,113 @@ export interface DownloadProgressDto {
   total_bytes: number;
   speed_bytes_per_sec: number;
   status: string;
+  queue_position?: number;
+  current_file_index: number;
+  total_files: number;
+  error_message?: string;
+}
+
+export interface MarkInstalledResultDto {
+  game: GameDto;
+  warning?: string;
+}
+
+export interface MinigalaxyImportDto {
+  found: boolean;
+  settings_imported: string[];
+  game_files_imported: number;
+  installed_games_registered: string[];
+  skipped: string[];
+}
+
+export interface MoveGameResultDto {
+  game: GameDto;
+  warnings: string[];
+}
+
+export interface MoveProgressDto {
+  game_id: number;
+  copied_bytes: number;
+  total_bytes: number;
+}
+
+export interface VerifyReportDto {
+  game_id: number;
+  ok: number; // number of files that matched
+  missing: string[];
+  corrupt: string[];
+}
+
+export interface RepairResultDto {
+  game: GameDto;
+  repaired: number; // number of files restored
+  failed: string[]; // files still missing or corrupt afterwards
+  reinstalled: boolean; // true when the installer was re-run instead
+}
+
+export interface InstallProgressDto {
+  game_id: number;
+  percent?: number; // absent while progress is unknown, e.g. Wine installers
+  elapsed_secs: number;
+}
+
+export interface DownloadInfoDto {
+  os: string;
+  language: string;
+  version?: string;
+  total_size: number;
+  file_sizes: number[];
+}
+
+export interface LanguageOptionDto {
+  language: string;
+  total_size: number; // bytes to download for this language
+}
+
+export interface PendingDownloadsDto {
+  resumed: number[];
+  paused: number[];
 }
 
 export interface GameInfoDto {
   id: number;
   title: string;
   description?: string;
-  changelog?: string;
+  changelog?: string; // raw HTML as served by GOG
+  changelog_plain?: string; // the changelog with tags stripped and entities decoded
   screenshots: string[];
+  dlcs: DlcDto[];
+}
+
+// Local copies of the GamesDB artwork; absent when GamesDB has none
+export interface GamesDbCachePathsDto {
+  cover?: string;
+  vertical_cover?: string;
+  background?: string;
+}
+
+export interface PlaytimeDto {
+  game_id: number;
+  total_seconds: number;
+  last_played: string | null; // RFC 3339
+}
+
+/**
+ * Every user-editable per-game setting, read and written in one go. Bookkeeping
+ * fields of GameInfo (installed version, language, checksums) are not part of it.
+ */
+export interface GameSettingsDto {
+  keep_installer: boolean | null; // null follows the global setting
+  interactive_install: boolean | null; // null follows the global setting
+  launch_arguments: string[];
+  environment: Record<string, string>;
+  compatibility_tool: string;
+  launch_target: string | null; // null autodetects
+  show_fps: boolean;
+  use_gamescope: boolean;
+  gamescope_options: string;
+  hidden: boolean;
+  favorite: boolean;
 }
 
 export interface GamesDbInfoDto {
@@ -68,6 +217,24 @@ export interface LaunchResultDto {
   pid?: number;
 }
 
+export interface LaunchReadinessDto {
+  ready: boolean;
+  reason?: string; // why the game can't be launched, when not ready
+}
+
+export interface LaunchDiagnosisDto {
+  game_id: number;
+  platform: string;
+  install_dir: string;
+  launcher_type?: string; // start_script, executable, wine or proton; absent when detection failed
+  command: string[];
+  cwd?: string;
+  environment: Record<string, string>; // variables set on top of Galaxi's own environment
+  wine_path?: string;
+  directories: { path: string; exists: boolean }[];
+  error_message?: string; // why the game couldn't be launched
+}
+
 export interface ConfigDto {
   locale: string;
   lang: string;
@@ -83,4 +250,6 @@ export interface ConfigDto {
   wine_debug: boolean;
   wine_disable_ntsync: boolean;
   wine_auto_install_dxvk: boolean;
+  interactive_install: boolean;
+  verify_downloads: boolean;
 }
@@ -6,8 +6,33 @@ export class GalaxiError extends Error {
   }
 }
 
+/**
+ * GOG answered with a non-2xx status. The status and (truncated) response
+ * body are kept so callers can tell "not found" from "try again later".
+ */
+export class HttpError extends GalaxiError {
+  constructor(public readonly status: number, public readonly body: string) {
+    super(describeHttpStatus(status), GalaxiErrorType.HttpError);
+    this.name = 'HttpError';
+  }
+}
+
+function describeHttpStatus(status: number): string {
+  if (status === 404) {
+    return 'GOG returned 404: not found';
+  }
+  if (status === 429) {
+    return 'GOG returned 429: too many requests, try again later';
+  }
+  if (status >= 500) {
+    return `GOG returned ${status}, try again later`;
+  }
+  return `GOG returned ${status}`;
+}
+
 export enum GalaxiErrorType {
   AuthError = 'AuthError',
+  TokenRevoked = 'TokenRevoked',
   NetworkError = 'NetworkError',
   DownloadError = 'DownloadError',
   InstallError = 'InstallError',
@@ -15,8 +40,10 @@ export enum GalaxiErrorType {
   ConfigError = 'ConfigError',
   FileSystemError = 'FileSystemError',
   ApiError = 'ApiError',
+  HttpError = 'HttpError',
   NoDownloadLinkFound = 'NoDownloadLinkFound',
   NotFoundError = 'NotFoundError',
+  NotOwned = 'NotOwned',
   Unknown = 'Unknown'
 }
 
@@ -0,0 +1,115 @@
+import { describe, expect, test } from 'bun:test';
+import * as fs from 'fs';
+import * as path from 'path';
+import { useTempDataDir, makeGame } from './testing';
+
+useTempDataDir();
+
+describe('getNewInstallDirectoryName', () => {
+  test('titles reducing to the same name get distinct directories with the id', () => {
+    const first = makeGame('Witcher: Enhanced', 1207664643);
+    const second = makeGame('Witcher Enhanced!', 1207664663);
+    expect(first.getInstallDirectoryName()).toBe(second.getInstallDirectoryName());
+
+    expect(first.getNewInstallDirectoryName(false)).toBe(second.getNewInstallDirectoryName(false));
+    expect(first.getNewInstallDirectoryName(true)).toBe('Witcher Enhanced (1207664643)');
+    expect(second.getNewInstallDirectoryName(true)).toBe('Witcher Enhanced (1207664663)');
+  });
+
+  test('titles with nothing left after sanitizing use the id', () => {
+    const game = makeGame('???', 42);
+    expect(game.getNewInstallDirectoryName(false)).toBe('42');
+    expect(game.getNewInstallDirectoryName(true)).toBe('42');
+  });
+});
+
+describe('game info', () => {
+  test('games with the same sanitized name keep separate settings', () => {
+    const first = makeGame('Witcher: Enhanced', 1);
+    const second = makeGame('Witcher Enhanced!', 2);
+    first.saveGameInfo({ language: 'de' });
+    second.saveGameInfo({ language: 'fr' });
+    expect(first.loadGameInfo().language).toBe('de');
+    expect(second.loadGameInfo().language).toBe('fr');
+  });
+
+  test('a legacy status file is read until the settings are saved again', () => {
+    const game = makeGame('Some Game', 3);
+    game.install_dir = '/games/Some Game';
+    const legacyFile = game.getLegacyStatusFilePath()!;
+    fs.mkdirSync(path.dirname(legacyFile), { recursive: true });
+    fs.writeFileSync(legacyFile, JSON.stringify({ language: 'pl' }));
+    expect(game.loadGameInfo().language).toBe('pl');
+
+    game.saveGameInfo({ ...game.loadGameInfo(), keep_installer: true });
+    expect(fs.existsSync(game.getStatusFilePath())).toBe(true);
+    expect(game.loadGameInfo()).toEqual({ language: 'pl', keep_installer: true });
+  });
+
+  test('concurrent writers of different fields both keep their value', async () => {
+    // Separate instances, like a cached game and one freshly loaded for an API call
+    const writer = async (key: 'language' | 'compatibility_tool', values: string[]) => {
+      const game = makeGame('Some Game', 8);
+      for (const value of values) {
+        game.setInfo(key, value);
+        await new Promise(resolve => setTimeout(resolve, 0));
+      }
+    };
+    await Promise.all([
+      writer('language', ['de', 'fr', 'pl']),
+      writer('compatibility_tool', ['wine', '/opt/proton', 'wine']),
+    ]);
+
+    const game = makeGame('Some Game', 8);
+    expect(game.loadGameInfo()).toEqual({ language: 'pl', compatibility_tool: 'wine' });
+    // Every write went through a temporary file that was renamed away
+    expect(fs.readdirSync(path.dirname(game.getStatusFilePath()))).toEqual(['8.json']);
+
+    game.setInfo('language', undefined);
+    expect(game.loadGameInfo()).toEqual({ compatibility_tool: 'wine' });
+  });
+
+  test('a legacy status file only belongs to the game installed in its directory', () => {
+    const owner = makeGame('Witcher: Enhanced', 9);
+    owner.install_dir = '/games/Witcher Enhanced';
+    const other = makeGame('Witcher Enhanced!', 10);
+    other.install_dir = '/games/Witcher Enhanced (10)';
+    const legacyFile = owner.getLegacyStatusFilePath()!;
+    fs.mkdirSync(path.dirname(legacyFile), { recursive: true });
+    fs.writeFileSync(legacyFile, JSON.stringify({ md5sum: { 'game.bin': 'abc' }, launch_target: 'game.bin' }));
+
+    expect(owner.loadGameInfo()).toEqual({ md5sum: { 'game.bin': 'abc' }, launch_target: 'game.bin' });
+    expect(other.getLegacyStatusFilePath()).toBeNull();
+    expect(other.loadGameInfo()).toEqual({});
+    expect(makeGame('Witcher Enhanced', 11).loadGameInfo()).toEqual({});
+  });
+
+  test('titles with nothing left after sanitizing have no legacy file', () => {
+    const game = makeGame('???', 4);
+    game.install_dir = '/games/4';
+    expect(game.getLegacyStatusFilePath()).toBeNull();
+  });
+});
+
+describe('isUpdateAvailable', () => {
+  test('a recorded installer version is compared with the one GOG serves', () => {
+    const game = makeGame('Some Game', 5);
+    game.saveGameInfo({ version: '2.0' });
+    expect(game.isUpdateAvailable({ installer_version: '2.1' })).toBe(true);
+    expect(game.isUpdateAvailable({ installer_version: '2.0' })).toBe(false);
+    expect(game.getUpdateVersions({ installer_version: '2.1' })).toEqual({ installed: '2.0', available: '2.1' });
+  });
+
+  test('builds are compared when both sides know theirs', () => {
+    const game = makeGame('Some Game', 6);
+    game.saveGameInfo({ version: '2.0', build_id: '100', build_version: '2.0' });
+    expect(game.isUpdateAvailable({ installer_version: '2.1', build_id: '100', build_version: '2.0' })).toBe(false);
+    expect(game.isUpdateAvailable({ installer_version: '2.0', build_id: '101', build_version: '2.0.1' })).toBe(true);
+    expect(game.getUpdateVersions({ installer_version: '2.0', build_id: '101', build_version: '2.0.1' }))
+      .toEqual({ installed: '2.0', available: '2.0.1' });
+  });
+
+  test('a game without a recorded version never has an update', () => {
+    expect(makeGame('Some Game', 7).isUpdateAvailable({ installer_version: '2.1' })).toBe(false);
+  });
+});
@@ -1,16 +1,81 @@
+import * as fs from 'fs';
+import * as path from 'path';
 import { GameDto, DlcDto } from './dto';
+import { getConfigGamesDir, getIconDir, getThumbnailDir } from './config';
+
+/**
+ * Per-game settings persisted as JSON in the config games directory.
+ * Every field is optional; an absent field means "use the global default".
+ */
+export interface GameInfo {
+  keep_installer?: boolean;
+  // Version field of the installer the game was installed from
+  version?: string;
+  // Content-system build the installed files are known to match, with its
+  // version name; absent when the installer can't be tied to a build
+  build_id?: string;
+  build_version?: string;
+  language?: string;
+  interactive_install?: boolean;
+  // Arguments for a silent Windows install, replacing the detected flags
+  installer_args?: string[];
+  // Extra arguments passed to the game executable, one entry per argument
+  launch_arguments?: string[];
+  // Extra environment variables for the game process
+  environment?: Record<string, string>;
+  // "wine" or the path of a Proton install used to run Windows games
+  compatibility_tool?: string;
+  // Wine prefix of this Windows game, instead of wine_prefix in its install
+  // directory. The game files live in its drive_c/game.
+  wine_prefix_path?: string;
+  // Executable (or .lnk for Windows games) picked by the user, relative to
+  // the game's files; autodetected when absent
+  launch_target?: string;
+  // Show an FPS overlay through the graphics drivers
+  show_fps?: boolean;
+  // Run the game inside a gamescope session, with extra gamescope flags
+  use_gamescope?: boolean;
+  gamescope_options?: string;
+  // "linux" or "windows": the build to download and run instead of the
+  // platform GOG lists the game under
+  preferred_platform?: string;
+  // MD5 of every installed file, keyed by path relative to the game files directory
+  md5sum?: Record<string, string>;
+  // Legacy whitespace-separated forms of the two fields above, as written by
+  // minigalaxy. Only read when the structured field is absent.
+  command?: string;
+  variable?: string;
+}
+
+/**
+ * What GOG serves now for a game. Installer versions and build ids are
+ * separate schemes and only ever compared with their own kind.
+ */
+export interface AvailableVersion {
+  installer_version: string;
+  // Newest public content-system build, for games that have builds
+  build_id?: string;
+  build_version?: string;
+}
+
+// Leading articles ignored when sorting titles
+const SORT_ARTICLES = /^(the|a|an)\s+/i;
+
+const TITLE_COLLATOR = new Intl.Collator(undefined, { sensitivity: 'base', numeric: true });
 
 export class Dlc {
   id: number;
   name: string;
   title: string;
   image_url: string;
+  installed: boolean;
 
-  constructor(id: number, name: string, title: string, imageUrl: string) {
+  constructor(id: number, name: string, title: string, imageUrl: string, installed: boolean = false) {
     this.id = id;
     this.name = name;
     this.title = title;
     this.image_url = imageUrl;
+    this.installed = installed;
   }
 
   toDto(): DlcDto {
@@ -19,6 +84,7 @@ export class Dlc {
       name: this.name,
       title: this.title,
       image_url: this.image_url,
+      installed: this.installed,
     };
   }
 }
@@ -77,7 +143,7 @@ export class Game {
       dto.platform,
       dto.category
     );
-    game.dlcs = dto.dlcs.map(d => new Dlc(d.id, d.name, d.title, d.image_url));
+    game.dlcs = dto.dlcs.map(d => new Dlc(d.id, d.name, d.title, d.image_url, d.installed ?? false));
     return game;
   }
 
@@ -89,6 +155,19 @@ export class Game {
     return Game.sanitizeFolderName(this.name);
   }
 
+  /**
+   * Folder name for a new install. With `includeId` the game id is appended
+   * so titles that sanitize to the same name don't share a directory; titles
+   * with nothing left after sanitizing always fall back to the id.
+   */
+  getNewInstallDirectoryName(includeId: boolean): string {
+    const name = this.getInstallDirectoryName();
+    if (!name) {
+      return String(this.id);
+    }
+    return includeId ? `${name} (${this.id})` : name;
+  }
+
   /**
    * Static method to sanitize a game name for use as a folder name.
    * Removes special characters that are not allowed in folder names (: * ? " < > |)
@@ -101,4 +180,193 @@ export class Game {
       .join('')
       .trim();
   }
+
+  /**
+   * Platform the game is downloaded, installed and run for: its
+   * preferred_platform, or the platform GOG lists it under
+   */
+  getInstallPlatform(info: GameInfo = this.loadGameInfo()): string {
+    return info.preferred_platform ?? this.platform;
+  }
+
+  /**
+   * Title used for sorting: leading articles ("The", "A", "An") removed
+   */
+  getSortName(): string {
+    return this.name.trim().replace(SORT_ARTICLES, '');
+  }
+
+  /**
+   * Library ordering: favorites first, then optionally installed games, then
+   * titles with locale-aware collation (so "Ä" sorts with "A"), then id so
+   * the order is deterministic.
+   */
+  static compare(a: Game, b: Game, installedFirst: boolean = true, favorites: Set<number> = new Set()): number {
+    const aFavorite = favorites.has(a.id) ? 0 : 1;
+    const bFavorite = favorites.has(b.id) ? 0 : 1;
+    if (aFavorite !== bFavorite) {
+      return aFavorite - bFavorite;
+    }
+    if (installedFirst) {
+      const aInstalled = a.install_dir ? 0 : 1;
+      const bInstalled = b.install_dir ? 0 : 1;
+      if (aInstalled !== bInstalled) {
+        return aInstalled - bInstalled;
+      }
+    }
+    const byName = TITLE_COLLATOR.compare(a.getSortName(), b.getSortName());
+    return byName !== 0 ? byName : a.id - b.id;
+  }
+
+  /**
+   * URL of the square cover shown in the library, or '' without an image
+   */
+  getThumbnailUrl(): string {
+    if (!this.image_url) {
+      return '';
+    }
+    const url = this.image_url.startsWith('//') ? `https:${this.image_url}` : this.image_url;
+    return `${url}_196.jpg`;
+  }
+
+  getThumbnailPath(): string {
+    return path.join(getThumbnailDir(), `${this.id}.jpg`);
+  }
+
+  getCachedIconPath(): string {
+    return path.join(getIconDir(), `${this.id}.jpg`);
+  }
+
+  /**
+   * Path of the JSON file holding this game's GameInfo. Keyed by id, as
+   * titles that sanitize to the same name would otherwise share one file.
+   */
+  getStatusFilePath(): string {
+    return path.join(getConfigGamesDir(), `${this.id}.json`);
+  }
+
+  /**
+   * Status file named after the install directory, as written by older
+   * versions and minigalaxy. Titles that sanitize to the same name share that
+   * file, so it only belongs to the game installed in the directory of that
+   * name; null for any other game and for titles with nothing left after
+   * sanitizing.
+   */
+  getLegacyStatusFilePath(): string | null {
+    const name = this.getInstallDirectoryName();
+    if (!name || !this.install_dir || path.basename(this.install_dir) !== name) {
+      return null;
+    }
+    return path.join(getConfigGamesDir(), `${name}.json`);
+  }
+
+  /**
+   * Settings from the id-keyed status file, falling back to the legacy file
+   * until the first save moves them over
+   */
+  loadGameInfo(): GameInfo {
+    let statusFile = this.getStatusFilePath();
+    if (!fs.existsSync(statusFile)) {
+      const legacyFile = this.getLegacyStatusFilePath();
+      if (!legacyFile || !fs.existsSync(legacyFile)) {
+        return {};
+      }
+      statusFile = legacyFile;
+    }
+    try {
+      return JSON.parse(fs.readFileSync(statusFile, 'utf-8')) as GameInfo;
+    } catch (error) {
+      console.warn(`Failed to read game info from ${statusFile}:`, error);
+      return {};
+    }
+  }
+
+  /**
+   * Write the info file through a temporary file and a rename, so a crash
+   * mid-write can't leave a truncated file behind and lose every setting.
+   */
+  saveGameInfo(info: GameInfo): void {
+    const statusFile = this.getStatusFilePath();
+    fs.mkdirSync(path.dirname(statusFile), { recursive: true });
+    const tempFile = `${statusFile}.${process.pid}.tmp`;
+    fs.writeFileSync(tempFile, JSON.stringify(info, null, 2));
+    fs.renameSync(tempFile, statusFile);
+  }
+
+  getInfo<K extends keyof GameInfo>(key: K): GameInfo[K] {
+    return this.loadGameInfo()[key];
+  }
+
+  /**
+   * Whether GOG serves something other than what is installed. Builds are
+   * compared when the installed build is known, installer versions otherwise.
+   */
+  isUpdateAvailable(available: AvailableVersion, info: GameInfo = this.loadGameInfo()): boolean {
+    if (info.build_id && available.build_id) {
+      return info.build_id !== available.build_id;
+    }
+    return !!info.version && !!available.installer_version && info.version !== available.installer_version;
+  }
+
+  /**
+   * Installed and available version to show for an update, of the kind
+   * isUpdateAvailable compared
+   */
+  getUpdateVersions(available: AvailableVersion, info: GameInfo = this.loadGameInfo()): { installed: string; available: string } {
+    if (info.build_id && available.build_id) {
+      return { installed: info.build_version ?? info.build_id, available: available.build_version ?? available.build_id };
+    }
+    return { installed: info.version ?? '', available: available.installer_version };
+  }
+
+  /**
+   * Arguments to append to the game's command line
+   */
+  getLaunchArguments(info: GameInfo = this.loadGameInfo()): string[] {
+    if (info.launch_arguments) {
+      return info.launch_arguments;
+    }
+    return info.command ? info.command.split(/\s+/).filter(arg => arg.length > 0) : [];
+  }
+
+  /**
+   * Environment variables to set for the game process
+   */
+  getLaunchEnvironment(info: GameInfo = this.loadGameInfo()): Record<string, string> {
+    if (info.environment) {
+      return info.environment;
+    }
+    const env: Record<string, string> = {};
+    for (const entry of (info.variable ?? '').split(/\s+/)) {
+      const separator = entry.indexOf('=');
+      if (separator > 0) {
+        env[entry.slice(0, separator)] = entry.slice(separator + 1);
+      }
+    }
+    return env;
+  }
+
+  /**
+   * Set a single GameInfo field. Passing undefined removes the override.
+   * The read-modify-write is synchronous on purpose: API calls can't
+   * interleave inside it, so concurrent updates of different fields of the
+   * same game are applied one after the other and none is lost. Keep it free
+   * of awaits.
+   */
+  setInfo<K extends keyof GameInfo>(key: K, value: GameInfo[K]): void {
+    const info = this.loadGameInfo();
+    setInfoField(info, key, value);
+    this.saveGameInfo(info);
+  }
+}
+
+/**
+ * Set a field on a loaded GameInfo, removing it when the value is undefined
+ */
+export function setInfoField<K extends keyof GameInfo>(info: GameInfo, key: K, value: GameInfo[K]): void {
+  if (value === undefined) {
+    delete info[key];
+  } else {
+    info[key] = value;
+  }
 }
@@ -0,0 +1,53 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import { AxiosInstance } from 'axios';
+
+// The ETag of each cached image is kept next to it, e.g. 123.jpg.etag
+const ETAG_SUFFIX = '.etag';
+
+/**
+ * Download an image into the cache, revalidating an already cached copy with
+ * a conditional GET so unchanged images aren't transferred again. Returns the
+ * local path. A stale copy is kept when the server can't be reached.
+ */
+export async function fetchCachedImage(client: AxiosInstance, url: string, dest: string): Promise<string> {
+  const etagPath = dest + ETAG_SUFFIX;
+  const cached = fs.existsSync(dest);
+
+  const headers: Record<string, string> = {};
+  if (cached) {
+    if (fs.existsSync(etagPath)) {
+      headers['If-None-Match'] = fs.readFileSync(etagPath, 'utf-8').trim();
+    }
+    headers['If-Modified-Since'] = fs.statSync(dest).mtime.toUTCString();
+  }
+
+  try {
+    const response = await client.get(url, {
+      headers,
+      responseType: 'arraybuffer',
+      validateStatus: status => status === 200 || status === 304,
+    });
+    if (response.status === 304) {
+      return dest;
+    }
+
+    fs.mkdirSync(path.dirname(dest), { recursive: true });
+    const tempPath = `${dest}.tmp`;
+    fs.writeFileSync(tempPath, Buffer.from(response.data));
+    fs.renameSync(tempPath, dest);
+
+    const etag = response.headers['etag'];
+    if (etag) {
+      fs.writeFileSync(etagPath, String(etag));
+    } else {
+      fs.rmSync(etagPath, { force: true });
+    }
+    return dest;
+  } catch (error) {
+    if (cached) {
+      return dest;
+    }
+    throw error;
+  }
+}
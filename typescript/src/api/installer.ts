@@ -1,16 +1,417 @@
 import * as fs from 'fs';
 import * as path from 'path';
 import * as child_process from 'child_process';
+import * as os from 'os';
 import { GalaxiError, GalaxiErrorType } from './error';
 import { Game } from './game';
-import { DownloadManager } from './download';
+import { DownloadManager, calculateMd5 } from './download';
+import { extractArchive, extractZipEntries, isArchive } from './archive';
+import { findInPath, isFlatpak, resolveTool, spawnTool, windowsRunner, wineDebugValue, WindowsRunner } from './tools';
 
 export interface WineOptions {
   prefix: string;
   executable: string;
   debug: boolean;
+  debug_channels?: string;
   disable_ntsync: boolean;
   auto_install_dxvk: boolean;
+  // Run the installer with its own UI instead of silently into c:\game
+  interactive?: boolean;
+  // "wine" (the default) or the path of a Proton install
+  compatibility_tool?: string;
+  // Arguments for a silent install instead of the flags for the installer's type
+  installer_args?: string[];
+}
+
+export type WindowsInstallerType = 'inno' | 'nsis';
+
+// Flags for an unattended install into c:\game, where the launcher looks for
+// the game. NSIS only accepts /D as the last argument.
+export const SILENT_INSTALLER_FLAGS: Record<WindowsInstallerType, string[]> = {
+  inno: ['/VERYSILENT', '/NORESTART', '/SUPPRESSMSGBOXES', '/DIR=c:\\game'],
+  nsis: ['/S', '/D=c:\\game'],
+};
+
+// How far into an installer its type's signature is looked for
+const INSTALLER_SIGNATURE_SCAN_BYTES = 8 * 1024 * 1024;
+
+/**
+ * Tell the installer framework of a Windows installer from the signature its
+ * stub carries. Most GOG installers are Inno Setup, which is also assumed
+ * when no signature is found.
+ */
+export function detectWindowsInstallerType(installerPath: string): WindowsInstallerType {
+  const fd = fs.openSync(installerPath, 'r');
+  try {
+    const buffer = Buffer.alloc(Math.min(INSTALLER_SIGNATURE_SCAN_BYTES, fs.fstatSync(fd).size));
+    fs.readSync(fd, buffer, 0, buffer.length, 0);
+    if (!buffer.includes('Inno Setup') && buffer.includes('Nullsoft')) {
+      return 'nsis';
+    }
+  } finally {
+    fs.closeSync(fd);
+  }
+  return 'inno';
+}
+
+/**
+ * Turn a user-supplied installer path (e.g. from a file picker) into an
+ * absolute, readable file path: strips a file:// prefix, expands ~, decodes
+ * percent-encoding when the literal path doesn't exist and resolves symlinks.
+ */
+export function normalizeInstallerPath(input: string): string {
+  let candidate = input.trim();
+  if (candidate.startsWith('file://')) {
+    candidate = candidate.slice('file://'.length);
+  }
+  if (candidate === '~' || candidate.startsWith('~/')) {
+    candidate = path.join(os.homedir(), candidate.slice(1));
+  }
+  candidate = path.resolve(candidate);
+
+  if (!fs.existsSync(candidate) && /%[0-9a-fA-F]{2}/.test(candidate)) {
+    try {
+      candidate = decodeURIComponent(candidate);
+    } catch {
+      // Not valid percent-encoding, keep the literal path
+    }
+  }
+
+  if (!fs.existsSync(candidate)) {
+    throw new GalaxiError(`Installer file not found: ${candidate}`, GalaxiErrorType.NotFoundError);
+  }
+
+  const resolved = fs.realpathSync(candidate);
+  if (!fs.statSync(resolved).isFile()) {
+    throw new GalaxiError(`Installer path is not a file: ${resolved}`, GalaxiErrorType.FileSystemError);
+  }
+  try {
+    fs.accessSync(resolved, fs.constants.R_OK);
+  } catch {
+    throw new GalaxiError(`Installer file is not readable: ${resolved}`, GalaxiErrorType.FileSystemError);
+  }
+
+  return resolved;
+}
+
+/**
+ * Check that an install directory looks like a valid installation for the platform.
+ * Returns a description of what is missing, or null when the files look fine.
+ * Windows games are looked for in the given Wine prefix, by default the one
+ * in the install directory.
+ */
+export function verifyGameFiles(
+  installDir: string,
+  platform: string,
+  winePrefix: string = path.join(installDir, 'wine_prefix')
+): string | null {
+  if (!fs.existsSync(installDir) || !fs.statSync(installDir).isDirectory()) {
+    return `Install directory does not exist: ${installDir}`;
+  }
+
+  if (platform === 'windows') {
+    const gameDir = path.join(winePrefix, 'drive_c', 'game');
+    if (!fs.existsSync(gameDir)) {
+      return `No Wine game directory found at ${gameDir}`;
+    }
+    return null;
+  }
+
+  if (fs.existsSync(path.join(installDir, 'start.sh'))) {
+    return null;
+  }
+  const hasExecutable = fs.readdirSync(installDir).some(f => {
+    try {
+      const stats = fs.statSync(path.join(installDir, f));
+      return stats.isFile() && (stats.mode & 0o111) !== 0;
+    } catch {
+      return false;
+    }
+  });
+  return hasExecutable ? null : `No start.sh or executable found in ${installDir}`;
+}
+
+/**
+ * Directory holding the game's own files: the install directory for Linux
+ * games, c:\game inside the Wine prefix for Windows games
+ */
+export function getGameFilesDir(
+  installDir: string,
+  platform: string,
+  winePrefix: string = path.join(installDir, 'wine_prefix')
+): string {
+  return platform === 'windows' ? path.join(winePrefix, 'drive_c', 'game') : installDir;
+}
+
+// Files Galaxi itself writes into the install directory
+const UNTRACKED_FILES = new Set(['galaxi-launch.log', 'galaxi-launch.log.1', 'galaxi-install.log']);
+
+// Wine output of the last Windows install, written while wine_debug is on
+const INSTALL_LOG_NAME = 'galaxi-install.log';
+
+/**
+ * Where a wine installer's output goes: the game's install log when
+ * debugging, nowhere otherwise so it doesn't flood the console
+ */
+function installerOutput(installDir: string, debug: boolean): 'ignore' | number {
+  if (!debug) {
+    return 'ignore';
+  }
+  fs.mkdirSync(installDir, { recursive: true });
+  return fs.openSync(path.join(installDir, INSTALL_LOG_NAME), 'w');
+}
+
+/**
+ * Relative paths of the regular files below a directory. Symlinks are not
+ * followed, so a Wine prefix's dosdevices don't lead outside it.
+ */
+async function listFiles(root: string, dir = root, files: string[] = []): Promise<string[]> {
+  for (const entry of await fs.promises.readdir(dir, { withFileTypes: true })) {
+    const fullPath = path.join(dir, entry.name);
+    if (entry.isDirectory()) {
+      await listFiles(root, fullPath, files);
+    } else if (entry.isFile() && !UNTRACKED_FILES.has(entry.name)) {
+      files.push(path.relative(root, fullPath));
+    }
+  }
+  return files;
+}
+
+/**
+ * MD5 of every file below a game files directory, keyed by relative path
+ */
+export async function hashInstalledFiles(dir: string): Promise<Record<string, string>> {
+  const md5sum: Record<string, string> = {};
+  for (const file of await listFiles(dir)) {
+    md5sum[file] = await calculateMd5(path.join(dir, file));
+  }
+  return md5sum;
+}
+
+export interface InstalledFilesReport {
+  ok: string[];
+  missing: string[];
+  corrupt: string[];
+}
+
+/**
+ * Compare the files below a game files directory against an md5sum map.
+ * Files not in the map (saves, configs written later) are ignored.
+ */
+export async function verifyInstalledFiles(dir: string, md5sum: Record<string, string>): Promise<InstalledFilesReport> {
+  const report: InstalledFilesReport = { ok: [], missing: [], corrupt: [] };
+  const present = new Set(fs.existsSync(dir) ? await listFiles(dir) : []);
+
+  for (const [file, expected] of Object.entries(md5sum)) {
+    if (!present.has(file)) {
+      report.missing.push(file);
+    } else if ((await calculateMd5(path.join(dir, file))) === expected.toLowerCase()) {
+      report.ok.push(file);
+    } else {
+      report.corrupt.push(file);
+    }
+  }
+  return report;
+}
+
+// Where mojosetup keeps the game files inside a GOG .sh installer's zip
+const LINUX_INSTALLER_DATA = 'data/noarch/';
+
+/**
+ * Whether individual files can be restored from an installer, rather than
+ * re-running it: true for Linux .sh installers and plain archives
+ */
+export function canRepairFromInstaller(installerPath: string): boolean {
+  const fileName = path.basename(installerPath);
+  return fileName.endsWith('.sh') || isArchive(fileName);
+}
+
+/**
+ * Restore the given files (relative to the game files directory) from the
+ * installer, leaving every other file alone. Returns the files written.
+ */
+export async function restoreFilesFromInstaller(installerPath: string, filesDir: string, files: string[]): Promise<string[]> {
+  const wanted = new Set(files);
+  const fileName = path.basename(installerPath).toLowerCase();
+
+  if (fileName.endsWith('.sh')) {
+    return extractZipEntries(installerPath, filesDir, name => {
+      const relative = name.startsWith(LINUX_INSTALLER_DATA) ? name.slice(LINUX_INSTALLER_DATA.length) : null;
+      return relative && wanted.has(relative) ? relative : null;
+    });
+  }
+  if (fileName.endsWith('.zip')) {
+    return extractZipEntries(installerPath, filesDir, name => (wanted.has(name) ? name : null));
+  }
+
+  // Tarballs can't be read selectively; unpack next to the game and copy back
+  const staging = `${filesDir}.repair`;
+  await fs.promises.rm(staging, { recursive: true, force: true });
+  try {
+    await extractArchive(installerPath, staging);
+    const restored: string[] = [];
+    for (const file of wanted) {
+      const source = path.join(staging, file);
+      if (!fs.existsSync(source)) {
+        continue;
+      }
+      const target = path.join(filesDir, file);
+      await fs.promises.mkdir(path.dirname(target), { recursive: true });
+      await fs.promises.rm(target, { force: true });
+      await fs.promises.rename(source, target);
+      restored.push(file);
+    }
+    return restored;
+  } finally {
+    await fs.promises.rm(staging, { recursive: true, force: true });
+  }
+}
+
+/**
+ * Total size in bytes of the regular files below a directory
+ */
+export async function getDirectorySize(dir: string): Promise<number> {
+  let total = 0;
+  for (const entry of await fs.promises.readdir(dir, { withFileTypes: true })) {
+    const fullPath = path.join(dir, entry.name);
+    if (entry.isDirectory()) {
+      total += await getDirectorySize(fullPath);
+    } else if (entry.isFile()) {
+      total += (await fs.promises.stat(fullPath)).size;
+    }
+  }
+  return total;
+}
+
+/**
+ * Recursively copy a directory, preserving file modes and symlinks
+ * (Wine prefixes rely on dosdevices symlinks). Reports copied bytes.
+ */
+export async function copyDirectory(src: string, dest: string, onBytes?: (bytes: number) => void): Promise<void> {
+  const stats = await fs.promises.stat(src);
+  await fs.promises.mkdir(dest, { recursive: true, mode: stats.mode });
+
+  for (const entry of await fs.promises.readdir(src, { withFileTypes: true })) {
+    const srcPath = path.join(src, entry.name);
+    const destPath = path.join(dest, entry.name);
+
+    if (entry.isSymbolicLink()) {
+      await fs.promises.symlink(await fs.promises.readlink(srcPath), destPath);
+    } else if (entry.isDirectory()) {
+      await copyDirectory(srcPath, destPath, onBytes);
+    } else if (entry.isFile()) {
+      await fs.promises.copyFile(srcPath, destPath);
+      const fileStats = await fs.promises.stat(srcPath);
+      await fs.promises.chmod(destPath, fileStats.mode);
+      if (onBytes) {
+        onBytes(fileStats.size);
+      }
+    }
+  }
+}
+
+/**
+ * Fix absolute paths inside a Wine prefix after its install directory moved.
+ * Drive symlinks and Proton's `pfx` link into the old directory are
+ * re-pointed and the registry's Z: paths are rewritten. Returns warnings for mappings that can't be fixed.
+ */
+export function relocateWinePrefix(winePrefix: string, oldDir: string, newDir: string): string[] {
+  const warnings: string[] = [];
+  if (!fs.existsSync(winePrefix)) {
+    return warnings;
+  }
+
+  const dosdevices = path.join(winePrefix, 'dosdevices');
+  if (fs.existsSync(dosdevices)) {
+    for (const device of fs.readdirSync(dosdevices)) {
+      const devicePath = path.join(dosdevices, device);
+      let target: string;
+      try {
+        target = fs.readlinkSync(devicePath);
+      } catch {
+        continue;
+      }
+      if (!path.isAbsolute(target) || target === '/') {
+        continue;
+      }
+      if (target === oldDir || target.startsWith(oldDir + path.sep)) {
+        fs.unlinkSync(devicePath);
+        fs.symlinkSync(newDir + target.slice(oldDir.length), devicePath);
+      } else {
+        warnings.push(`Drive ${device} maps to absolute path ${target}, which was not moved`);
+      }
+    }
+  }
+
+  // Proton's compat data sits next to the prefix with `pfx` linked to it
+  const protonPfx = path.join(`${winePrefix}-proton`, 'pfx');
+  try {
+    const target = fs.readlinkSync(protonPfx);
+    if (target === oldDir || target.startsWith(oldDir + path.sep)) {
+      fs.unlinkSync(protonPfx);
+      fs.symlinkSync(newDir + target.slice(oldDir.length), protonPfx);
+    }
+  } catch {
+    // No Proton compat data for this game
+  }
+
+  // Registry files store paths as Z:\\home\\... with escaped backslashes
+  const toRegistryPath = (p: string) => 'Z:' + p.replace(/\//g, '\\\\');
+  const oldRegistryPath = toRegistryPath(oldDir);
+  const newRegistryPath = toRegistryPath(newDir);
+  for (const regFile of ['system.reg', 'user.reg', 'userdef.reg']) {
+    const regPath = path.join(winePrefix, regFile);
+    if (!fs.existsSync(regPath)) {
+      continue;
+    }
+    try {
+      const content = fs.readFileSync(regPath, 'utf-8');
+      if (content.includes(oldRegistryPath)) {
+        fs.writeFileSync(regPath, content.split(oldRegistryPath).join(newRegistryPath));
+      }
+    } catch (error: any) {
+      warnings.push(`Could not update ${regFile}: ${error.message}`);
+    }
+  }
+
+  return warnings;
+}
+
+/**
+ * Directories an installer is likely to create under drive_c: top-level
+ * folders and the children of the usual "Program Files" / "GOG Games" roots.
+ */
+function listInstallLocations(driveC: string): string[] {
+  if (!fs.existsSync(driveC)) {
+    return [];
+  }
+  const roots = ['Program Files', 'Program Files (x86)', 'GOG Games', 'Games'];
+  const locations: string[] = [];
+  for (const entry of fs.readdirSync(driveC)) {
+    const fullPath = path.join(driveC, entry);
+    if (!fs.statSync(fullPath).isDirectory()) {
+      continue;
+    }
+    if (roots.includes(entry)) {
+      for (const child of fs.readdirSync(fullPath)) {
+        const childPath = path.join(fullPath, child);
+        if (fs.statSync(childPath).isDirectory()) {
+          locations.push(childPath);
+        }
+      }
+    } else if (!['users', 'windows', 'ProgramData'].includes(entry)) {
+      locations.push(fullPath);
+    }
+  }
+  return locations;
+}
+
+function containsExecutable(dir: string): boolean {
+  try {
+    return fs.readdirSync(dir).some(f => f.toLowerCase().endsWith('.exe'));
+  } catch {
+    return false;
+  }
 }
 
 export class GameInstaller {
@@ -24,7 +425,8 @@ export class GameInstaller {
     game: Game,
     installerPath: string,
     installDir: string,
-    wineOptions?: WineOptions
+    wineOptions?: WineOptions,
+    onProgress?: (percent: number) => void
   ): Promise<void> {
     // Create install directory
     if (!fs.existsSync(installDir)) {
@@ -45,9 +447,11 @@ export class GameInstaller {
     // Make executable for Linux installers
     if (fileName.endsWith('.sh')) {
       fs.chmodSync(installerPath, 0o755);
-      await this.runLinuxInstaller(installerPath, installDir);
+      await this.runLinuxInstaller(installerPath, installDir, onProgress);
     } else if (fileName.endsWith('.exe') && wineOptions) {
       await this.runWindowsInstaller(installerPath, installDir, wineOptions);
+    } else if (isArchive(fileName)) {
+      await extractArchive(installerPath, installDir);
     } else {
       throw new GalaxiError(
         `Unsupported installer type: ${fileName}`,
@@ -56,9 +460,25 @@ export class GameInstaller {
     }
   }
 
-  private async runLinuxInstaller(installerPath: string, installDir: string): Promise<void> {
+  private async runLinuxInstaller(
+    installerPath: string,
+    installDir: string,
+    onProgress?: (percent: number) => void
+  ): Promise<void> {
     return new Promise((resolve, reject) => {
-      const process = child_process.spawn(installerPath, ['--', `--i-agree-to-all-licenses`, `--noreadme`, `--nooptions`, `--noprompt`, `--destination=${installDir}`]);
+      const process = child_process.spawn(installerPath, ['--', `--i-agree-to-all-licenses`, `--noreadme`, `--nooptions`, `--noprompt`, `--destination=${installDir}`], {
+        stdio: ['ignore', 'pipe', 'pipe'],
+      });
+
+      // mojosetup prints its progress as a percentage; report the latest one
+      process.stdout.on('data', (chunk: Buffer) => {
+        const matches = chunk.toString().match(/\d{1,3}(?=%)/g);
+        if (matches && onProgress) {
+          onProgress(Math.min(100, parseInt(matches[matches.length - 1], 10)));
+        }
+      });
+      // Drain stderr so the installer never blocks on a full pipe
+      process.stderr.resume();
 
       process.on('close', (code) => {
         if (code === 0) {
@@ -85,33 +505,65 @@ export class GameInstaller {
     installDir: string,
     wineOptions: WineOptions
   ): Promise<void> {
-    // Set up Wine prefix inside the game install directory
+    // Set up Wine prefix inside the game install directory, unless one was
+    // chosen elsewhere; Wine initializes it in the created directory
     const winePrefix = wineOptions.prefix || path.join(installDir, 'wine_prefix');
+    fs.mkdirSync(winePrefix, { recursive: true });
+    const runner = windowsRunner(wineOptions.compatibility_tool, wineOptions.executable, winePrefix);
 
     const env: any = {
       ...process.env,
-      WINEPREFIX: winePrefix,
+      ...runner.env,
     };
 
     if (wineOptions.disable_ntsync) {
       env.WINE_DISABLE_FAST_SYNC = '1';
     }
 
-    // Auto-install DXVK and setup Wine prefix if requested
-    if (wineOptions.auto_install_dxvk) {
+    env.WINEDEBUG = wineDebugValue(wineOptions.debug, wineOptions.debug_channels);
+    const output = installerOutput(installDir, wineOptions.debug);
+    try {
+      await this.runWineInstaller(installerPath, winePrefix, wineOptions, runner, env, output);
+    } finally {
+      if (typeof output === 'number') {
+        fs.closeSync(output);
+      }
+    }
+  }
+
+  private async runWineInstaller(
+    installerPath: string,
+    winePrefix: string,
+    wineOptions: WineOptions,
+    runner: WindowsRunner,
+    env: any,
+    output: 'ignore' | number
+  ): Promise<void> {
+    // Auto-install DXVK and setup Wine prefix if requested. Proton sets up
+    // its own prefix and ships DXVK, so this only applies to plain Wine.
+    if (wineOptions.auto_install_dxvk && !runner.isProton) {
       await this.setupWinePrefix(winePrefix, wineOptions.executable, wineOptions.disable_ntsync);
     }
 
+    if (wineOptions.interactive) {
+      await this.runInteractiveWindowsInstaller(installerPath, winePrefix, runner.command, runner.args, env, output);
+      return;
+    }
+
+    // Install to c:\game inside the Wine prefix (which maps to wine_prefix/drive_c/game)
+    // unless the arguments were overridden for the game
+    const installerArgs = wineOptions.installer_args?.length
+      ? wineOptions.installer_args
+      : SILENT_INSTALLER_FLAGS[detectWindowsInstallerType(installerPath)];
+
     return new Promise((resolve, reject) => {
-      const wineExec = wineOptions.executable || 'wine';
-      console.log('Running Wine installer...');
-      // Install to c:\game inside the Wine prefix (which maps to wine_prefix/drive_c/game)
-      const process = child_process.spawn(
-        wineExec, 
-        [installerPath, '/VERYSILENT', '/NORESTART', '/SUPPRESSMSGBOXES', '/DIR=c:\\game'], 
+      console.log(runner.isProton ? 'Running installer with Proton...' : 'Running Wine installer...');
+      const process = spawnTool(
+        runner.command, 
+        [...runner.args, installerPath, ...installerArgs], 
         { 
           env,
-          stdio: ['ignore', 'ignore', 'ignore'] // Ignore all stdio to prevent console flooding
+          stdio: ['ignore', output, output]
         }
       );
 
@@ -136,7 +588,68 @@ export class GameInstaller {
     });
   }
 
-  private async setupWinePrefix(winePrefix: string, wineExecutable: string, disableNtsync: boolean): Promise<void> {
+  /**
+   * Run the installer with its normal UI so the user can pick components and
+   * the install location, wait for it to exit, then find the directory it
+   * installed into and link it as c:\game where the launcher expects it.
+   */
+  private async runInteractiveWindowsInstaller(
+    installerPath: string,
+    winePrefix: string,
+    command: string,
+    commandArgs: string[],
+    env: any,
+    output: 'ignore' | number
+  ): Promise<void> {
+    const driveC = path.join(winePrefix, 'drive_c');
+    const before = new Set(listInstallLocations(driveC));
+
+    await new Promise<void>((resolve, reject) => {
+      console.log('Running Wine installer interactively...');
+      const proc = spawnTool(command, [...commandArgs, installerPath], {
+        env,
+        stdio: ['ignore', output, output],
+      });
+      console.log(`Interactive installer running with PID ${proc.pid}`);
+
+      proc.on('close', (code) => {
+        if (code === 0) {
+          resolve();
+        } else {
+          reject(new GalaxiError(
+            `Wine installer exited with code ${code}`,
+            GalaxiErrorType.InstallError
+          ));
+        }
+      });
+
+      proc.on('error', (err) => {
+        reject(new GalaxiError(
+          `Wine installer failed: ${err.message}`,
+          GalaxiErrorType.InstallError
+        ));
+      });
+    });
+
+    const gameLink = path.join(driveC, 'game');
+    if (fs.existsSync(gameLink)) {
+      // The user picked c:\game (or it already existed)
+      return;
+    }
+
+    const created = listInstallLocations(driveC).filter(dir => !before.has(dir) && containsExecutable(dir));
+    if (created.length === 0) {
+      throw new GalaxiError(
+        'Could not detect where the installer placed the game. Please select the game directory manually.',
+        GalaxiErrorType.InstallError
+      );
+    }
+
+    console.log(`Interactive install detected at ${created[0]}`);
+    fs.symlinkSync(created[0], gameLink);
+  }
+
+  async setupWinePrefix(winePrefix: string, wineExecutable: string, disableNtsync: boolean): Promise<void> {
     const env: any = {
       ...process.env,
       WINEPREFIX: winePrefix,
@@ -153,10 +666,49 @@ export class GameInstaller {
       const wineboot = wineExec.replace('wine', 'wineboot');
       let resolved = false;
       
-      const proc = child_process.spawn(wineboot, ['--init'], { 
-        env,
-        stdio: ['ignore', 'ignore', 'ignore'] // Ignore all stdio to prevent console flooding
-      });
+      const runFallback = () => {
+        if (resolved) {
+          return;
+        }
+        // Try with 'wine wineboot' if wineboot is not found
+        console.log('Trying fallback: wine wineboot --init');
+        let fallbackProc: child_process.ChildProcess;
+        try {
+          fallbackProc = spawnTool(wineExec, ['wineboot', '--init'], {
+            env,
+            stdio: ['ignore', 'ignore', 'ignore']
+          });
+        } catch (err: any) {
+          console.warn(`Failed to initialize Wine prefix: ${err.message}`);
+          resolved = true;
+          resolve();
+          return;
+        }
+        fallbackProc.on('close', () => {
+          if (!resolved) {
+            resolved = true;
+            console.log('Wine prefix initialized (fallback)');
+            resolve();
+          }
+        });
+        fallbackProc.on('error', () => {
+          if (!resolved) {
+            resolved = true;
+            resolve();
+          }
+        });
+      };
+
+      let proc: child_process.ChildProcess;
+      try {
+        proc = spawnTool(wineboot, ['--init'], { 
+          env,
+          stdio: ['ignore', 'ignore', 'ignore'] // Ignore all stdio to prevent console flooding
+        });
+      } catch {
+        runFallback();
+        return;
+      }
 
       proc.on('close', (code) => {
         if (!resolved) {
@@ -166,29 +718,7 @@ export class GameInstaller {
         }
       });
 
-      proc.on('error', () => {
-        if (!resolved) {
-          // Try with 'wine wineboot' if wineboot is not found
-          console.log('Trying fallback: wine wineboot --init');
-          const fallbackProc = child_process.spawn(wineExec, ['wineboot', '--init'], { 
-            env,
-            stdio: ['ignore', 'ignore', 'ignore']
-          });
-          fallbackProc.on('close', () => {
-            if (!resolved) {
-              resolved = true;
-              console.log('Wine prefix initialized (fallback)');
-              resolve();
-            }
-          });
-          fallbackProc.on('error', () => {
-            if (!resolved) {
-              resolved = true;
-              resolve();
-            }
-          });
-        }
-      });
+      proc.on('error', runFallback);
     });
 
     // Ensure winetricks is available (download if needed)
@@ -209,10 +739,17 @@ export class GameInstaller {
           WINE: wineExecutable || 'wine',
         };
 
-        const proc = child_process.spawn(winetricksPath, ['-q', component], { 
-          env: winetricksEnv,
-          stdio: ['ignore', 'ignore', 'ignore'] // Ignore all stdio to prevent console flooding
-        });
+        let proc: child_process.ChildProcess;
+        try {
+          proc = spawnTool(winetricksPath, ['-q', component], { 
+            env: winetricksEnv,
+            stdio: ['ignore', 'ignore', 'ignore'] // Ignore all stdio to prevent console flooding
+          });
+        } catch (err: any) {
+          console.warn(`Warning: Failed to run winetricks ${component}: ${err.message}`);
+          resolve();
+          return;
+        }
 
         proc.on('close', (code: number) => {
           if (code !== 0) {
@@ -234,22 +771,19 @@ export class GameInstaller {
 
   private async ensureWinetricks(): Promise<string | null> {
     // First check if winetricks is in PATH
-    try {
-      const result = await new Promise<string | null>((resolve) => {
-        child_process.exec('which winetricks', (error, stdout) => {
-          if (!error && stdout) {
-            resolve(stdout.trim());
-          } else {
-            resolve(null);
-          }
-        });
-      });
-      
-      if (result) {
-        return result;
+    const localWinetricks = findInPath('winetricks');
+    if (localWinetricks) {
+      return localWinetricks;
+    }
+
+    // Inside a Flatpak, a host-installed winetricks is run through flatpak-spawn
+    if (isFlatpak()) {
+      try {
+        resolveTool('winetricks');
+        return 'winetricks';
+      } catch (err) {
+        // Continue to download
       }
-    } catch (err) {
-      // Continue to download
     }
 
     // Download winetricks to cache directory
@@ -3,14 +3,24 @@ import * as path from 'path';
 import * as child_process from 'child_process';
 import { GalaxiError, GalaxiErrorType } from './error';
 import { Game } from './game';
-import { LaunchResultDto } from './dto';
+import { LaunchResultDto, LaunchDiagnosisDto } from './dto';
 import { BINARY_NAMES_TO_IGNORE } from './config';
+import { spawnTool, resolveTool, windowsRunner, findInPath, wineDebugValue } from './tools';
 
 export interface WineLaunchOptions {
   wine_prefix: string;
   wine_executable: string;
   wine_debug: boolean;
+  wine_debug_channels?: string;
   wine_disable_ntsync: boolean;
+  // "wine" (the default) or the path of a Proton install
+  compatibility_tool?: string;
+  // User patterns skipped when looking for the game executable, on top of
+  // BINARY_NAMES_TO_IGNORE
+  ignored_binaries?: string[];
+  // Native DOSBox/ScummVM used for games bundling them; PATH when empty
+  dosbox_executable?: string;
+  scummvm_executable?: string;
 }
 
 export interface LaunchResult {
@@ -19,21 +29,198 @@ export interface LaunchResult {
   pid?: number;
 }
 
+// Combined stdout/stderr of the last launch; the one before is kept with a .1 suffix
+const LAUNCH_LOG_NAME = 'galaxi-launch.log';
+
+export function getLaunchLogPath(game: Game): string {
+  return path.join(game.install_dir, LAUNCH_LOG_NAME);
+}
+
+/**
+ * Start a fresh launch log for the game, keeping the previous one, and
+ * return a file descriptor to hand to the game as stdout/stderr.
+ * The caller closes it once the process has been spawned.
+ */
+function openLaunchLog(game: Game, command: string[]): number {
+  const logPath = getLaunchLogPath(game);
+  try {
+    if (fs.existsSync(logPath)) {
+      fs.renameSync(logPath, `${logPath}.1`);
+    }
+  } catch (error) {
+    console.warn(`Failed to rotate launch log ${logPath}:`, error);
+  }
+
+  const fd = fs.openSync(logPath, 'w');
+  fs.writeSync(fd, `[galaxi] ${new Date().toISOString()} launching: ${command.join(' ')}\n`);
+  return fd;
+}
+
+/**
+ * Arguments to run the game through gamescope, or null when it isn't enabled.
+ * Checked before spawning so a missing gamescope is reported as such.
+ */
+function gamescopeArgs(game: Game): string[] | null {
+  if (!game.getInfo('use_gamescope')) {
+    return null;
+  }
+  resolveTool('gamescope');
+  const options = (game.getInfo('gamescope_options') ?? '').split(/\s+/).filter(opt => opt.length > 0);
+  return [...options, '--'];
+}
+
+/**
+ * Spawn a detached game process with its output going to the launch log.
+ *
+ * Wrappers are applied outermost first: gamescope starts the nested
+ * compositor and runs everything after its `--` inside it, so any other
+ * wrapper (and wine itself for Windows games) comes after gamescope and
+ * before the game command.
+ */
+function spawnLogged(
+  game: Game,
+  command: string,
+  args: string[],
+  options: child_process.SpawnOptions,
+  spawner: (command: string, args: string[], options: child_process.SpawnOptions) => child_process.ChildProcess
+): child_process.ChildProcess {
+  const gamescope = gamescopeArgs(game);
+  if (gamescope) {
+    args = [...gamescope, command, ...args];
+    command = 'gamescope';
+    spawner = spawnTool;
+  }
+
+  const logFd = openLaunchLog(game, [command, ...args]);
+  try {
+    return spawner(command, args, {
+      ...options,
+      detached: true,
+      stdio: ['ignore', logFd, logFd],
+    });
+  } finally {
+    // The child has its own copy of the descriptor
+    fs.closeSync(logFd);
+  }
+}
+
+/**
+ * What launching a game would run, worked out without starting anything
+ */
+export interface LaunchPlan {
+  launcher_type: 'start_script' | 'executable' | 'wine' | 'proton' | 'dosbox' | 'scummvm';
+  command: string;
+  args: string[];
+  cwd: string;
+  // Only the variables Galaxi sets on top of its own environment
+  env: Record<string, string>;
+  // The wine binary or proton script for Windows games
+  wine_path?: string;
+}
+
+/**
+ * Work out how to launch the game. With `prepare` off nothing is created on
+ * disk, so this can be used to inspect a launch.
+ */
+export function planLaunch(game: Game, wineOptions?: WineLaunchOptions, prepare: boolean = true): LaunchPlan {
+  let plan: LaunchPlan;
+  const platform = game.getInstallPlatform();
+  if (platform === 'linux') {
+    plan = planLinuxLaunch(game);
+  } else if (platform === 'windows' && wineOptions) {
+    plan = planWindowsLaunch(game, wineOptions, prepare);
+  } else {
+    throw new GalaxiError('Unsupported platform or missing Wine options', GalaxiErrorType.LaunchError);
+  }
+  // Per-game variables set by the user still win
+  plan.env = { ...fpsDisplayEnv(game), ...plan.env };
+  return plan;
+}
+
+/**
+ * Variables enabling the FPS overlay of Mesa, NVIDIA and DXVK. They are only
+ * added to the game's own environment, never to Galaxi's.
+ */
+export function fpsDisplayEnv(game: Game): Record<string, string> {
+  if (!game.getInfo('show_fps')) {
+    return {};
+  }
+  const env: Record<string, string> = {
+    GALLIUM_HUD: 'simple,fps',
+    VK_INSTANCE_LAYERS: 'VK_LAYER_MESA_overlay',
+    __GL_SHOW_GRAPHICS_OSD: '1',
+  };
+  if (game.getInstallPlatform() === 'windows') {
+    env.DXVK_HUD = 'fps';
+  }
+  return env;
+}
+
+// Names of the programs launchers depend on, for error messages
+const LAUNCH_TOOL_NAMES: Partial<Record<LaunchPlan['launcher_type'], string>> = {
+  wine: 'Wine',
+  dosbox: 'DOSBox',
+  scummvm: 'ScummVM',
+};
+
+/**
+ * Check that the program a launch plan runs is there, so a missing Wine or
+ * game executable is reported by name instead of as a spawn error
+ */
+function requireLaunchCommand(plan: LaunchPlan): void {
+  const tool = LAUNCH_TOOL_NAMES[plan.launcher_type];
+  if (!tool) {
+    // Proton's script was checked while planning
+    if (plan.launcher_type !== 'proton' && !isExecutableFile(plan.command)) {
+      throw new GalaxiError(`Game executable not found or not executable: ${plan.command}`, GalaxiErrorType.LaunchError);
+    }
+    return;
+  }
+  try {
+    resolveTool(plan.command);
+  } catch (error: any) {
+    throw new GalaxiError(`${tool} is needed to run this game: ${error.message}`, GalaxiErrorType.LaunchError);
+  }
+}
+
+/**
+ * Why the game can't be launched right now, or null when everything it needs
+ * is in place. Nothing is created or started.
+ */
+export function checkLaunchReadiness(game: Game, wineOptions?: WineLaunchOptions): string | null {
+  try {
+    const plan = planLaunch(game, wineOptions, false);
+    requireLaunchCommand(plan);
+    gamescopeArgs(game);
+    return null;
+  } catch (error: any) {
+    return error.message;
+  }
+}
+
 export async function launchGame(
   game: Game,
   wineOptions?: WineLaunchOptions
 ): Promise<LaunchResult> {
   try {
-    if (game.platform === 'linux') {
-      return await launchLinuxGame(game);
-    } else if (game.platform === 'windows' && wineOptions) {
-      return await launchWindowsGame(game, wineOptions);
-    } else {
-      return {
-        success: false,
-        error_message: 'Unsupported platform or missing Wine options',
-      };
+    const plan = planLaunch(game, wineOptions);
+    requireLaunchCommand(plan);
+    if (plan.wine_path) {
+      console.log(`Launching Windows game: ${game.name}`);
+      console.log(`Command: ${[plan.command, ...plan.args].join(' ')}`);
     }
+
+    const proc = spawnLogged(game, plan.command, plan.args, {
+      cwd: plan.cwd,
+      env: { ...process.env, ...plan.env },
+    }, game.getInstallPlatform() === 'windows' ? spawnTool : child_process.spawn);
+
+    proc.unref();
+
+    return {
+      success: true,
+      pid: proc.pid,
+    };
   } catch (error: any) {
     return {
       success: false,
@@ -42,7 +229,52 @@ export async function launchGame(
   }
 }
 
-async function launchLinuxGame(game: Game): Promise<LaunchResult> {
+/**
+ * Directories the launcher looks in for the game, in order
+ */
+export function launchDirectories(game: Game, wineOptions?: WineLaunchOptions): string[] {
+  if (game.getInstallPlatform() !== 'windows') {
+    return [game.install_dir, path.join(game.install_dir, 'game')];
+  }
+  const winePrefix = getWinePrefix(game, wineOptions);
+  return [game.install_dir, winePrefix, path.join(winePrefix, 'drive_c', 'game')];
+}
+
+function getWinePrefix(game: Game, wineOptions?: WineLaunchOptions): string {
+  return wineOptions?.wine_prefix || path.join(game.install_dir, 'wine_prefix');
+}
+
+/**
+ * Report what launching the game would do without starting it or changing
+ * anything, for bug reports about games that won't start.
+ */
+export function diagnoseLaunch(game: Game, wineOptions?: WineLaunchOptions): LaunchDiagnosisDto {
+  const diagnosis: LaunchDiagnosisDto = {
+    game_id: game.id,
+    platform: game.getInstallPlatform(),
+    install_dir: game.install_dir,
+    command: [],
+    environment: {},
+    directories: launchDirectories(game, wineOptions).map(dir => ({ path: dir, exists: fs.existsSync(dir) })),
+  };
+
+  try {
+    const plan = planLaunch(game, wineOptions, false);
+    const gamescope = gamescopeArgs(game);
+    diagnosis.launcher_type = plan.launcher_type;
+    diagnosis.command = gamescope
+      ? ['gamescope', ...gamescope, plan.command, ...plan.args]
+      : [plan.command, ...plan.args];
+    diagnosis.cwd = plan.cwd;
+    diagnosis.environment = plan.env;
+    diagnosis.wine_path = plan.wine_path ? findInPath(plan.wine_path) ?? plan.wine_path : undefined;
+  } catch (error: any) {
+    diagnosis.error_message = error.message;
+  }
+  return diagnosis;
+}
+
+function planLinuxLaunch(game: Game): LaunchPlan {
   const installDir = game.install_dir;
   
   if (!fs.existsSync(installDir)) {
@@ -52,61 +284,62 @@ async function launchLinuxGame(game: Game): Promise<LaunchResult> {
     );
   }
 
-  // Find start script
-  const startScript = path.join(installDir, 'start.sh');
-  if (!fs.existsSync(startScript)) {
-    // Look for any executable
-    const files = fs.readdirSync(installDir);
-    const executable = files.find(f => {
-      const filePath = path.join(installDir, f);
-      try {
-        const stats = fs.statSync(filePath);
-        return stats.isFile() && (stats.mode & 0o111) !== 0;
-      } catch {
-        return false;
+  const target = chosenLaunchTarget(game, installDir);
+  if (!target) {
+    // Find start script: start.sh, a plain `start`, or the game's primary play task
+    for (const name of ['start.sh', 'start']) {
+      const startScript = path.join(installDir, name);
+      if (isExecutableFile(startScript)) {
+        return {
+          launcher_type: 'start_script',
+          command: startScript,
+          args: game.getLaunchArguments(),
+          cwd: installDir,
+          env: game.getLaunchEnvironment(),
+        };
       }
-    });
-
-    if (!executable) {
-      throw new GalaxiError(
-        'No executable found in game directory',
-        GalaxiErrorType.LaunchError
-      );
     }
 
-    const execPath = path.join(installDir, executable);
-    const proc = child_process.spawn(execPath, [], {
-      cwd: installDir,
-      detached: true,
-      stdio: 'ignore',
-    });
-
-    proc.unref();
-
-    return {
-      success: true,
-      pid: proc.pid,
-    };
+    const task = findPrimaryPlayTask([installDir, path.join(installDir, 'game')]);
+    if (task && isExecutableFile(task.path)) {
+      return {
+        launcher_type: 'start_script',
+        command: task.path,
+        args: [...task.arguments, ...game.getLaunchArguments()],
+        cwd: task.workingDir,
+        env: game.getLaunchEnvironment(),
+      };
+    }
   }
 
-  const proc = child_process.spawn(startScript, [], {
-    cwd: installDir,
-    detached: true,
-    stdio: 'ignore',
-  });
+  // Look for any executable, also in game/ where MojoSetup puts the binaries
+  const gameSubdir = path.join(installDir, 'game');
+  const executable = target
+    ?? findLinuxExecutables(installDir)[0]
+    ?? (fs.existsSync(gameSubdir) ? findLinuxExecutables(gameSubdir).map(f => path.join('game', f))[0] : undefined);
 
-  proc.unref();
+  if (!executable) {
+    throw new GalaxiError(
+      'No executable found in game directory',
+      GalaxiErrorType.LaunchError
+    );
+  }
 
+  const execPath = path.join(installDir, executable);
   return {
-    success: true,
-    pid: proc.pid,
+    launcher_type: 'executable',
+    command: execPath,
+    args: game.getLaunchArguments(),
+    cwd: path.dirname(execPath),
+    env: game.getLaunchEnvironment(),
   };
 }
 
-async function launchWindowsGame(
+function planWindowsLaunch(
   game: Game,
-  wineOptions: WineLaunchOptions
-): Promise<LaunchResult> {
+  wineOptions: WineLaunchOptions,
+  prepare: boolean
+): LaunchPlan {
   const installDir = game.install_dir;
   
   if (!fs.existsSync(installDir)) {
@@ -117,11 +350,9 @@ async function launchWindowsGame(
   }
 
   // Windows games are installed to wine_prefix/drive_c/game inside the install directory
-  const winePrefix = wineOptions.wine_prefix || path.join(installDir, 'wine_prefix');
+  const winePrefix = getWinePrefix(game, wineOptions);
   const gameDir = path.join(winePrefix, 'drive_c', 'game');
   
-  console.log(`Looking for game executable in: ${gameDir}`);
-  
   if (!fs.existsSync(gameDir)) {
     throw new GalaxiError(
       `Game directory not found at ${gameDir}`,
@@ -129,61 +360,145 @@ async function launchWindowsGame(
     );
   }
 
-  // Find Windows executable
-  const exeFiles = findExecutables(gameDir);
-  
-  if (exeFiles.length === 0) {
-    throw new GalaxiError(
-      'No Windows executable found',
-      GalaxiErrorType.LaunchError
-    );
+  const target = chosenLaunchTarget(game, gameDir);
+
+  // DOS and adventure games bundle DOSBox/ScummVM; a native build runs them better than wine
+  if (!target) {
+    const nativePlan = planDosboxLaunch(game, gameDir, wineOptions) ?? planScummvmLaunch(game, gameDir, wineOptions);
+    if (nativePlan) {
+      return nativePlan;
+    }
   }
 
-  // Filter out known installer/utility executables
-  const filteredExes = exeFiles.filter(exe => {
-    const basename = path.basename(exe).toLowerCase();
-    return !BINARY_NAMES_TO_IGNORE.some(ignore => basename === ignore.toLowerCase());
-  });
+  let exePath: string;
+  if (target) {
+    exePath = path.join(gameDir, target);
+    if (!fs.existsSync(exePath)) {
+      throw new GalaxiError(`The chosen executable no longer exists: ${exePath}`, GalaxiErrorType.LaunchError);
+    }
+  } else {
+    // Find Windows executable
+    const exeFiles = findExecutables(gameDir);
+    
+    if (exeFiles.length === 0) {
+      throw new GalaxiError(
+        'No Windows executable found',
+        GalaxiErrorType.LaunchError
+      );
+    }
+
+    // Filter out known installer/utility executables
+    const filteredExes = exeFiles.filter(exe => !isIgnoredBinary(exe, wineOptions.ignored_binaries));
 
-  const exePath = filteredExes[0] || exeFiles[0];
-  
-  console.log(`Launching Windows game: ${game.name}`);
-  console.log(`Executable: ${exePath}`);
-  console.log(`Wine prefix: ${winePrefix}`);
+    exePath = filteredExes[0] || exeFiles[0];
+  }
   
-  const env: any = {
-    ...process.env,
-    WINEPREFIX: winePrefix,
-  };
+  const runner = windowsRunner(wineOptions.compatibility_tool, wineOptions.wine_executable, winePrefix, prepare);
+  const env: Record<string, string> = { ...runner.env };
 
   if (wineOptions.wine_disable_ntsync) {
     env.WINE_DISABLE_FAST_SYNC = '1';
   }
 
-  if (wineOptions.wine_debug) {
-    env.WINEDEBUG = '+all';
-  } else {
-    env.WINEDEBUG = '-all';
-  }
+  // The output ends up in the launch log
+  env.WINEDEBUG = wineDebugValue(wineOptions.wine_debug, wineOptions.wine_debug_channels);
 
-  const wineExec = wineOptions.wine_executable || 'wine';
-  
-  const proc = child_process.spawn(wineExec, [exePath], {
+  // Per-game variables win over the defaults above
+  Object.assign(env, game.getLaunchEnvironment());
+
+  // Shortcuts aren't executables; wine's start resolves them
+  const program = exePath.toLowerCase().endsWith('.lnk') ? ['start', '/unix', exePath] : [exePath];
+
+  return {
+    launcher_type: runner.isProton ? 'proton' : 'wine',
+    command: runner.command,
+    args: [...runner.args, ...program, ...game.getLaunchArguments()],
     cwd: path.dirname(exePath),
     env,
-    detached: true,
-    stdio: 'ignore',
-  });
+    wine_path: runner.command,
+  };
+}
+
+/**
+ * Resolve a native DOSBox/ScummVM: the configured binary, else PATH. Null
+ * when it isn't installed, so the game falls back to its bundled Windows build.
+ */
+function nativeTool(configured: string | undefined, program: string): string | null {
+  if (configured) {
+    return configured;
+  }
+  try {
+    resolveTool(program);
+    return program;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Entry in a directory matching a name case-insensitively, GOG isn't
+ * consistent about "DOSBOX" vs "dosbox"
+ */
+function findEntry(dir: string, predicate: (name: string) => boolean): string | undefined {
+  return fs.readdirSync(dir).find(name => predicate(name.toLowerCase()));
+}
+
+function planDosboxLaunch(game: Game, gameDir: string, wineOptions: WineLaunchOptions): LaunchPlan | null {
+  if (!findEntry(gameDir, name => name === 'dosbox')) {
+    return null;
+  }
+  const dosbox = nativeTool(wineOptions.dosbox_executable, 'dosbox');
+  if (!dosbox) {
+    return null;
+  }
+
+  // GOG ships a shared dosbox<game>.conf plus a dosbox<game>_single.conf that starts the game
+  const confs = fs.readdirSync(gameDir).filter(name => /^dosbox.*\.conf$/i.test(name));
+  const single = confs.find(name => /_single\.conf$/i.test(name));
+  const main = confs.find(name => name !== single);
 
-  proc.unref();
+  const args: string[] = [];
+  for (const conf of [main, single]) {
+    if (conf) {
+      args.push('-conf', path.join(gameDir, conf));
+    }
+  }
+  args.push('-no-console', '-c', 'exit');
+
+  return {
+    launcher_type: 'dosbox',
+    command: dosbox,
+    args: [...args, ...game.getLaunchArguments()],
+    cwd: gameDir,
+    env: game.getLaunchEnvironment(),
+  };
+}
+
+function planScummvmLaunch(game: Game, gameDir: string, wineOptions: WineLaunchOptions): LaunchPlan | null {
+  if (!findEntry(gameDir, name => name === 'scummvm')) {
+    return null;
+  }
+  const scummvm = nativeTool(wineOptions.scummvm_executable, 'scummvm');
+  if (!scummvm) {
+    return null;
+  }
+
+  // The .ini next to the game names the ScummVM target to start
+  const ini = findEntry(gameDir, name => name.endsWith('.ini'));
+  if (!ini) {
+    return null;
+  }
 
   return {
-    success: true,
-    pid: proc.pid,
+    launcher_type: 'scummvm',
+    command: scummvm,
+    args: ['-c', path.join(gameDir, ini), ...game.getLaunchArguments()],
+    cwd: gameDir,
+    env: game.getLaunchEnvironment(),
   };
 }
 
-function findExecutables(dir: string, exeFiles: string[] = []): string[] {
+function findExecutables(dir: string, exeFiles: string[] = [], extensions: string[] = ['.exe']): string[] {
   const files = fs.readdirSync(dir);
   
   for (const file of files) {
@@ -191,11 +506,119 @@ function findExecutables(dir: string, exeFiles: string[] = []): string[] {
     const stats = fs.statSync(fullPath);
     
     if (stats.isDirectory()) {
-      findExecutables(fullPath, exeFiles);
-    } else if (stats.isFile() && file.toLowerCase().endsWith('.exe')) {
+      findExecutables(fullPath, exeFiles, extensions);
+    } else if (stats.isFile() && extensions.some(ext => file.toLowerCase().endsWith(ext))) {
       exeFiles.push(fullPath);
     }
   }
   
   return exeFiles;
 }
+
+/**
+ * Executable files directly in a native game's directory
+ */
+function findLinuxExecutables(dir: string): string[] {
+  return fs.readdirSync(dir).filter(f => isExecutableFile(path.join(dir, f)));
+}
+
+function isExecutableFile(filePath: string): boolean {
+  try {
+    const stats = fs.statSync(filePath);
+    return stats.isFile() && (stats.mode & 0o111) !== 0;
+  } catch {
+    return false;
+  }
+}
+
+export interface PlayTask {
+  path: string;
+  arguments: string[];
+  workingDir: string;
+}
+
+/**
+ * The primary file play task from the first goggame-<id>.info found in the
+ * given directories, with paths resolved against the info file's directory.
+ */
+export function findPrimaryPlayTask(dirs: string[]): PlayTask | null {
+  for (const dir of dirs) {
+    if (!fs.existsSync(dir)) {
+      continue;
+    }
+    for (const name of fs.readdirSync(dir).filter(f => /^goggame-\d+\.info$/.test(f))) {
+      let info: any;
+      try {
+        info = JSON.parse(fs.readFileSync(path.join(dir, name), 'utf-8'));
+      } catch (error) {
+        console.warn(`Failed to parse ${name}:`, error);
+        continue;
+      }
+      const tasks: any[] = (info.playTasks ?? []).filter((t: any) => t.type === 'FileTask' && t.path);
+      const task = tasks.find(t => t.isPrimary) ?? tasks[0];
+      if (!task) {
+        continue;
+      }
+      // Paths in GOG's info files use Windows separators
+      const taskPath = path.join(dir, task.path.replace(/\\/g, '/'));
+      return {
+        path: taskPath,
+        arguments: (task.arguments ?? '').split(/\s+/).filter((arg: string) => arg.length > 0),
+        workingDir: task.workingDir ? path.join(dir, task.workingDir.replace(/\\/g, '/')) : path.dirname(taskPath),
+      };
+    }
+  }
+  return null;
+}
+
+/**
+ * Whether an executable is a known helper tool rather than the game. Names
+ * match case-insensitively and may use * and ? wildcards.
+ */
+export function isIgnoredBinary(filePath: string, extraPatterns: string[] = []): boolean {
+  const basename = path.basename(filePath);
+  return [...BINARY_NAMES_TO_IGNORE, ...extraPatterns].some(pattern => globToRegExp(pattern).test(basename));
+}
+
+function globToRegExp(pattern: string): RegExp {
+  const source = pattern
+    .split('')
+    .map(c => (c === '*' ? '.*' : c === '?' ? '.' : c.replace(/[.+^${}()|[\]\\]/g, '\\$&')))
+    .join('');
+  return new RegExp(`^${source}$`, 'i');
+}
+
+/**
+ * The user's chosen launch target relative to the game's files, if it is set
+ * and still exists
+ */
+function chosenLaunchTarget(game: Game, gameDir: string): string | undefined {
+  const target = game.getInfo('launch_target');
+  if (!target) {
+    return undefined;
+  }
+  if (!fs.existsSync(path.join(gameDir, target))) {
+    console.warn(`Launch target ${target} of ${game.name} no longer exists, autodetecting`);
+    return undefined;
+  }
+  return target;
+}
+
+/**
+ * Everything the user can choose to launch, relative to the game's files:
+ * executables and .lnk shortcuts of Windows games minus known helper tools,
+ * or the executables in a native game's directory.
+ */
+export function listLaunchCandidates(game: Game, wineOptions?: WineLaunchOptions): string[] {
+  if (game.getInstallPlatform() !== 'windows') {
+    return fs.existsSync(game.install_dir) ? findLinuxExecutables(game.install_dir).sort() : [];
+  }
+  const gameDir = path.join(getWinePrefix(game, wineOptions), 'drive_c', 'game');
+  if (!fs.existsSync(gameDir)) {
+    return [];
+  }
+  return findExecutables(gameDir, [], ['.exe', '.lnk'])
+    .filter(file => !isIgnoredBinary(file, wineOptions?.ignored_binaries))
+    .map(file => path.relative(gameDir, file))
+    .sort();
+}
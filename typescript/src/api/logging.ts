@@ -0,0 +1,87 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import * as util from 'util';
+import { getLogDir } from './config';
+import { GalaxiError, GalaxiErrorType } from './error';
+
+// Most to least severe; a level also lets through everything before it
+export const LOG_LEVELS: string[] = ['error', 'warn', 'info', 'debug'];
+
+// The log file is rotated once it grows past this, keeping this many old ones
+const MAX_LOG_FILE_SIZE = 5 * 1024 * 1024;
+const KEPT_LOG_FILES = 3;
+
+// Level of each console method once logging is installed
+const CONSOLE_LEVELS: Record<string, string> = {
+  error: 'error',
+  warn: 'warn',
+  log: 'info',
+  info: 'info',
+  debug: 'debug',
+};
+
+let currentLevel = 'info';
+let installed = false;
+
+export function getLogFilePath(): string {
+  return path.join(getLogDir(), 'galaxi.log');
+}
+
+/**
+ * Shift galaxi.log to galaxi.log.1, galaxi.log.1 to galaxi.log.2 and so on,
+ * dropping the oldest
+ */
+function rotateLogFile(file: string): void {
+  for (let i = KEPT_LOG_FILES - 1; i >= 1; i--) {
+    if (fs.existsSync(`${file}.${i}`)) {
+      fs.renameSync(`${file}.${i}`, `${file}.${i + 1}`);
+    }
+  }
+  fs.renameSync(file, `${file}.1`);
+}
+
+function writeLogLine(level: string, args: unknown[]): void {
+  const file = getLogFilePath();
+  try {
+    fs.mkdirSync(path.dirname(file), { recursive: true });
+    if (fs.existsSync(file) && fs.statSync(file).size >= MAX_LOG_FILE_SIZE) {
+      rotateLogFile(file);
+    }
+    fs.appendFileSync(file, `${new Date().toISOString()} ${level.toUpperCase()} ${util.format(...args)}\n`);
+  } catch {
+    // A log that can't be written must never break the code that logs
+  }
+}
+
+/**
+ * Send console output at or above the given level to the log file as well,
+ * so it survives in a packaged app where nobody sees the backend's output.
+ * Messages below the level are dropped. Calling it again only changes the
+ * level.
+ */
+export function initLogging(level: string): void {
+  const normalized = level.trim().toLowerCase();
+  if (!LOG_LEVELS.includes(normalized)) {
+    throw new GalaxiError(
+      `Unknown log level: ${level} (expected one of ${LOG_LEVELS.join(', ')})`,
+      GalaxiErrorType.ConfigError
+    );
+  }
+  currentLevel = normalized;
+  if (installed) {
+    return;
+  }
+  installed = true;
+
+  const target = console as unknown as Record<string, (...args: unknown[]) => void>;
+  for (const [method, methodLevel] of Object.entries(CONSOLE_LEVELS)) {
+    const original = target[method].bind(console);
+    target[method] = (...args: unknown[]) => {
+      if (LOG_LEVELS.indexOf(methodLevel) > LOG_LEVELS.indexOf(currentLevel)) {
+        return;
+      }
+      original(...args);
+      writeLogLine(methodLevel, args);
+    };
+  }
+}
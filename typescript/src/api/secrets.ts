@@ -0,0 +1,155 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import * as crypto from 'crypto';
+import * as child_process from 'child_process';
+import { getDataDir } from './config';
+import { findInPath } from './tools';
+import { GalaxiError, GalaxiErrorType } from './error';
+
+// Stored secrets look like enc:v1:<iv>:<auth tag>:<ciphertext>, all base64
+const ENCRYPTED_PREFIX = 'enc:v1:';
+const CIPHER = 'aes-256-gcm';
+const KEY_BYTES = 32;
+const IV_BYTES = 12;
+
+// Attributes identifying the key in the Secret Service keyring
+const KEYRING_ATTRIBUTES = ['application', 'galaxi', 'purpose', 'token-encryption'];
+const KEY_FILE_NAME = 'secret.key';
+// Written once the key is known to live in the keyring, so a keyring that
+// is only temporarily unreachable isn't mistaken for having none
+const KEYRING_MARKER_NAME = 'secret.keyring';
+
+let cachedKey: Buffer | null = null;
+
+function keyringMarkerPath(): string {
+  return path.join(getDataDir(), KEYRING_MARKER_NAME);
+}
+
+function markKeyInKeyring(): void {
+  try {
+    fs.mkdirSync(getDataDir(), { recursive: true });
+    fs.writeFileSync(keyringMarkerPath(), '');
+  } catch (error) {
+    console.warn('Failed to record that the key is in the keyring:', error);
+  }
+}
+
+/**
+ * Read the key from the desktop keyring through libsecret's secret-tool,
+ * storing a fresh one there if none exists. Returns null when no keyring
+ * is available. A failed lookup only counts as "no key" when secret-tool
+ * says so (exit status 1 without output); otherwise, e.g. with a locked
+ * keyring or a D-Bus timeout, a key stored there earlier is not replaced,
+ * as that would make every stored token unreadable.
+ */
+function keyFromKeyring(): Buffer | null {
+  const secretTool = findInPath('secret-tool');
+  if (!secretTool) {
+    return null;
+  }
+
+  const lookup = child_process.spawnSync(secretTool, ['lookup', ...KEYRING_ATTRIBUTES], {
+    encoding: 'utf-8',
+    timeout: 5000,
+  });
+  if (lookup.status === 0 && lookup.stdout.trim().length > 0) {
+    const key = Buffer.from(lookup.stdout.trim(), 'base64');
+    if (key.length === KEY_BYTES) {
+      markKeyInKeyring();
+      return key;
+    }
+  }
+
+  const notFound = lookup.status === 1 && !lookup.stdout?.trim() && !lookup.stderr?.trim();
+  if (!notFound && lookup.status !== 0) {
+    const reason = lookup.stderr?.trim() || lookup.error?.message || `secret-tool exited with status ${lookup.status}`;
+    if (fs.existsSync(keyringMarkerPath())) {
+      throw new GalaxiError(`Could not read the token encryption key from the keyring: ${reason}`, GalaxiErrorType.ConfigError);
+    }
+    // No key was ever stored there, so the file key is safe to use
+    console.warn(`Keyring unavailable, keeping the key in a file: ${reason}`);
+    return null;
+  }
+
+  const key = crypto.randomBytes(KEY_BYTES);
+  const store = child_process.spawnSync(
+    secretTool,
+    ['store', '--label=Galaxi token encryption key', ...KEYRING_ATTRIBUTES],
+    { input: key.toString('base64'), timeout: 5000 }
+  );
+  if (store.status !== 0) {
+    return null;
+  }
+  markKeyInKeyring();
+  return key;
+}
+
+/**
+ * Fallback key kept next to the database, readable only by the user
+ */
+function keyFromFile(): Buffer {
+  const keyPath = path.join(getDataDir(), KEY_FILE_NAME);
+  if (fs.existsSync(keyPath)) {
+    const key = Buffer.from(fs.readFileSync(keyPath, 'utf-8').trim(), 'base64');
+    if (key.length !== KEY_BYTES) {
+      throw new GalaxiError(`Invalid key file ${keyPath}`, GalaxiErrorType.ConfigError);
+    }
+    fs.chmodSync(keyPath, 0o600);
+    return key;
+  }
+
+  const key = crypto.randomBytes(KEY_BYTES);
+  fs.mkdirSync(path.dirname(keyPath), { recursive: true });
+  fs.writeFileSync(keyPath, key.toString('base64'), { mode: 0o600 });
+  return key;
+}
+
+function getKey(): Buffer {
+  if (!cachedKey) {
+    const keyPath = path.join(getDataDir(), KEY_FILE_NAME);
+    // Once a key file exists it stays the source of truth, so tokens written
+    // while the keyring was unavailable can still be read
+    cachedKey = (!fs.existsSync(keyPath) && keyFromKeyring()) || keyFromFile();
+  }
+  return cachedKey;
+}
+
+export function isEncrypted(value: string): boolean {
+  return value.startsWith(ENCRYPTED_PREFIX);
+}
+
+/**
+ * Encrypt a secret for storage. Empty values stay empty.
+ */
+export function encryptSecret(plaintext: string): string {
+  if (!plaintext || isEncrypted(plaintext)) {
+    return plaintext;
+  }
+  const iv = crypto.randomBytes(IV_BYTES);
+  const cipher = crypto.createCipheriv(CIPHER, getKey(), iv);
+  const ciphertext = Buffer.concat([cipher.update(plaintext, 'utf-8'), cipher.final()]);
+  return ENCRYPTED_PREFIX + [iv, cipher.getAuthTag(), ciphertext].map(b => b.toString('base64')).join(':');
+}
+
+/**
+ * Decrypt a stored secret. Values written before encryption was introduced
+ * are returned as they are. A value that can't be decrypted (e.g. a database
+ * restored on another machine) yields an empty string, so the account simply
+ * has to log in again.
+ */
+export function decryptSecret(stored: string): string {
+  if (!stored || !isEncrypted(stored)) {
+    return stored;
+  }
+  // A key that can't be fetched right now is an error, not a lost token
+  const key = getKey();
+  try {
+    const [iv, tag, ciphertext] = stored.slice(ENCRYPTED_PREFIX.length).split(':').map(p => Buffer.from(p, 'base64'));
+    const decipher = crypto.createDecipheriv(CIPHER, key, iv);
+    decipher.setAuthTag(tag);
+    return Buffer.concat([decipher.update(ciphertext), decipher.final()]).toString('utf-8');
+  } catch (error) {
+    console.warn('Failed to decrypt stored secret:', error);
+    return '';
+  }
+}
@@ -0,0 +1,170 @@
+import { beforeEach, describe, expect, test } from 'bun:test';
+import * as fs from 'fs';
+import * as path from 'path';
+import {
+  APP_STATE,
+  checkForUpdate,
+  downloadGame,
+  getGameInfo,
+  installGame,
+  removeInstallerFiles,
+  resumeDownload,
+} from './simple';
+import { DownloadManager, DownloadStatus } from './download';
+import { dlcsDb, downloadsDb, gamesDb } from './database';
+import { AvailableVersion } from './game';
+import { GameInstaller } from './installer';
+import { DownloadInfo, GameInfoResponse, GogApi } from './gog_api';
+import { useTempDataDir, makeGame, servingClient } from './testing';
+
+const dataDir = useTempDataDir({ database: true });
+
+beforeEach(() => {
+  APP_STATE.config.install_dir = path.join(dataDir.dir, 'games');
+  APP_STATE.gamesCache.clear();
+  APP_STATE.installerFiles.clear();
+  APP_STATE.selectedInstallers.clear();
+  APP_STATE.api = undefined;
+});
+
+function installerInfo(data: Buffer): DownloadInfo {
+  return {
+    os: 'linux',
+    language: 'en',
+    version: '1.0',
+    total_size: data.length,
+    files: [{ size: data.length, downlink: 'https://api.gog.com/downlink' }],
+  };
+}
+
+// GOG serving one installer file for every game
+function stubApi(data: Buffer, fileName: string): GogApi {
+  return {
+    getDownloadInfo: async () => installerInfo(data),
+    getDlcDownloadInfo: async () => installerInfo(data),
+    getDownloadLink: async () => ({ url: `https://cdn.gog.com/${fileName}` }),
+  } as unknown as GogApi;
+}
+
+describe('downloadGame', () => {
+  test('a second download in the same session waits for its own transfer', async () => {
+    const data = Buffer.from('game data');
+    APP_STATE.downloadManager = new DownloadManager(servingClient(data));
+    APP_STATE.api = stubApi(data, 'game.tar.gz');
+    APP_STATE.gamesCache.set(1, makeGame('Some Game', 1));
+
+    const first = await downloadGame(1);
+    expect(fs.readFileSync(first)).toEqual(data);
+
+    // Like installGame removing the installer, so it's downloaded again
+    fs.rmSync(first);
+    const second = await downloadGame(1);
+    expect(second).toBe(first);
+    expect(fs.readFileSync(second)).toEqual(data);
+  });
+});
+
+describe('getGameInfo', () => {
+  test('a game stored in the database is found before the library is loaded', async () => {
+    gamesDb().saveGame({
+      id: 2,
+      name: 'Some Game',
+      url: '',
+      install_dir: '',
+      image_url: '',
+      platform: 'linux',
+      category: '',
+      dlcs: [{ id: 20, name: 'some_dlc', title: 'Some DLC', image_url: '' }],
+    });
+    dlcsDb().setDlcInstalled(20, true);
+    const info: GameInfoResponse = {
+      id: 2,
+      title: 'Some Game',
+      expanded_dlcs: [{ id: 20, title: 'Some DLC', slug: 'some_dlc' }],
+    };
+    APP_STATE.api = {
+      getInfo: async () => info,
+      getDlcs: GogApi.prototype.getDlcs,
+    } as unknown as GogApi;
+
+    const result = await getGameInfo(2);
+    expect(result.title).toBe('Some Game');
+    expect(result.dlcs.map(dlc => [dlc.id, dlc.installed])).toEqual([[20, true]]);
+    expect(APP_STATE.gamesCache.get(2)?.name).toBe('Some Game');
+  });
+});
+
+describe('resumeDownload', () => {
+  test('a DLC download from a previous session resumes for its game and language', async () => {
+    const data = Buffer.from('dlc data');
+    const game = makeGame('Some Game', 2);
+    APP_STATE.gamesCache.set(2, game);
+    const requested: unknown[][] = [];
+    APP_STATE.api = {
+      ...stubApi(data, 'dlc.tar.gz'),
+      getDownloadInfo: async () => {
+        throw new Error('the game itself was requested');
+      },
+      getDlcDownloadInfo: async (forGame: unknown, dlcId: number, language?: string) => {
+        requested.push([forGame, dlcId, language]);
+        return installerInfo(data);
+      },
+    } as unknown as GogApi;
+
+    downloadsDb().saveDownload(20, DownloadStatus.Paused, 0, data.length, null, 2, 'de');
+    APP_STATE.downloadManager = new DownloadManager(servingClient(data));
+    APP_STATE.downloadManager.restore(downloadsDb().getAllDownloads());
+
+    await resumeDownload(20);
+    expect(requested).toEqual([[game, 20, 'de']]);
+    await APP_STATE.downloadManager.stopAll();
+  });
+});
+
+describe('installed version', () => {
+  test('an install records the version it installed for checkForUpdate', async () => {
+    const data = Buffer.from('game data');
+    const game = makeGame('Some Game', 3);
+    APP_STATE.gamesCache.set(3, game);
+    APP_STATE.downloadManager = new DownloadManager(servingClient(data));
+    // Installing only creates the directory; what is recorded is under test
+    APP_STATE.installer = {
+      installGame: async (_game: unknown, _installer: string, installDir: string) => {
+        fs.mkdirSync(installDir, { recursive: true });
+      },
+    } as unknown as GameInstaller;
+    let available: AvailableVersion = { installer_version: '1.0', build_id: '100', build_version: '1.0' };
+    APP_STATE.api = {
+      ...stubApi(data, 'game.tar.gz'),
+      getAvailableVersion: async () => available,
+    } as unknown as GogApi;
+
+    await installGame(3, await downloadGame(3));
+    expect(game.loadGameInfo()).toMatchObject({ version: '1.0', build_id: '100', build_version: '1.0' });
+    expect(await checkForUpdate(3)).toBe(false);
+
+    available = { installer_version: '1.1', build_id: '101', build_version: '1.1' };
+    expect(await checkForUpdate(3)).toBe(true);
+  });
+});
+
+describe('removeInstallerFiles', () => {
+  test("another game's installer and in-progress download survive", async () => {
+    const downloadsDir = path.join(APP_STATE.config.install_dir, '.downloads');
+    fs.mkdirSync(downloadsDir, { recursive: true });
+    const names = [
+      'setup_one.exe',
+      'setup_one-1.bin',
+      'setup_one-2.bin',
+      'setup_two.exe',
+      'setup_two-1.bin.part',
+      'setup_two-1.bin.part1',
+    ];
+    for (const name of names) {
+      fs.writeFileSync(path.join(downloadsDir, name), name);
+    }
+
+    await removeInstallerFiles(1, path.join(downloadsDir, 'setup_one.exe'));
+    expect(fs.readdirSync(downloadsDir).sort()).toEqual(['setup_two-1.bin.part', 'setup_two-1.bin.part1', 'setup_two.exe']);
+  });
+});
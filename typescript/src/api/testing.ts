@@ -0,0 +1,74 @@
+import { beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import * as crypto from 'crypto';
+import { Readable } from 'stream';
+import { AxiosInstance } from 'axios';
+import { Game } from './game';
+import { initDatabase, closeDatabase } from './database';
+
+// Preloaded for every test run (see bunfig.toml): the data, cache and config
+// directories point into a temporary directory before any module loads, so
+// no test touches the user's own database, logs or keyring
+const sessionRoot = fs.mkdtempSync(path.join(os.tmpdir(), 'galaxi-test-'));
+process.env.XDG_DATA_HOME = path.join(sessionRoot, 'data');
+process.env.XDG_CACHE_HOME = path.join(sessionRoot, 'cache');
+process.env.XDG_CONFIG_HOME = path.join(sessionRoot, 'config');
+writeKeyFile(path.join(process.env.XDG_DATA_HOME, 'galaxi'));
+process.on('exit', () => fs.rmSync(sessionRoot, { recursive: true, force: true }));
+
+/**
+ * A key file makes token encryption use it instead of the desktop keyring
+ */
+function writeKeyFile(dir: string): void {
+  fs.mkdirSync(dir, { recursive: true });
+  fs.writeFileSync(path.join(dir, 'secret.key'), crypto.randomBytes(32).toString('base64'), { mode: 0o600 });
+}
+
+export interface TempDataDir {
+  // The galaxi data directory of the running test
+  readonly dir: string;
+}
+
+/**
+ * Give every test of the file a fresh data directory, with the database
+ * opened in it when `database` is set. Call once at the top of a test file.
+ */
+export function useTempDataDir(options: { database?: boolean } = {}): TempDataDir {
+  const current = { dir: '' };
+  const sessionDataHome = process.env.XDG_DATA_HOME;
+
+  beforeEach(() => {
+    const dataHome = fs.mkdtempSync(path.join(sessionRoot, 'data-'));
+    process.env.XDG_DATA_HOME = dataHome;
+    current.dir = path.join(dataHome, 'galaxi');
+    writeKeyFile(current.dir);
+    if (options.database) {
+      initDatabase();
+    }
+  });
+
+  afterEach(() => {
+    if (options.database) {
+      closeDatabase();
+    }
+    fs.rmSync(path.dirname(current.dir), { recursive: true, force: true });
+    process.env.XDG_DATA_HOME = sessionDataHome;
+  });
+
+  return current;
+}
+
+export function makeGame(name: string, id: number, platform: string = 'linux'): Game {
+  return new Game(name, '', id, '', '', platform, '');
+}
+
+/**
+ * An HTTP client answering every request with the whole of `data`
+ */
+export function servingClient(data: Buffer): AxiosInstance {
+  return {
+    request: async () => ({ status: 200, headers: { 'content-length': String(data.length) }, data: Readable.from([data]) }),
+  } as unknown as AxiosInstance;
+}
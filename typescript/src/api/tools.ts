@@ -0,0 +1,201 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import * as child_process from 'child_process';
+import * as os from 'os';
+import { GalaxiError, GalaxiErrorType } from './error';
+
+export interface ResolvedTool {
+  command: string;
+  args: string[];
+  viaHost: boolean;
+}
+
+/**
+ * Whether the backend is running inside a Flatpak sandbox
+ */
+export function isFlatpak(): boolean {
+  return fs.existsSync('/.flatpak-info');
+}
+
+/**
+ * Look up an executable in PATH (or accept it directly if it's a path).
+ * Returns the full path, or null when not found.
+ */
+export function findInPath(program: string): string | null {
+  if (program.includes('/')) {
+    return isExecutable(program) ? program : null;
+  }
+
+  const dirs = (process.env.PATH || '').split(':').filter(d => d);
+  for (const dir of dirs) {
+    const candidate = path.join(dir, program);
+    if (isExecutable(candidate)) {
+      return candidate;
+    }
+  }
+  return null;
+}
+
+function isExecutable(filePath: string): boolean {
+  try {
+    const stats = fs.statSync(filePath);
+    return stats.isFile() && (stats.mode & 0o111) !== 0;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Check whether a command is available on the host outside the Flatpak sandbox
+ */
+function hostHasCommand(program: string): boolean {
+  try {
+    const result = child_process.spawnSync('flatpak-spawn', ['--host', 'which', program], {
+      stdio: 'ignore',
+      timeout: 5000,
+    });
+    return result.status === 0;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Resolve how to run a tool. Inside the sandbox first, then — when running
+ * as a Flatpak — on the host through `flatpak-spawn --host`.
+ * Throws a LaunchError explaining where the tool was looked for if missing.
+ */
+export function resolveTool(program: string): ResolvedTool {
+  const local = findInPath(program);
+  if (local) {
+    return { command: local, args: [], viaHost: false };
+  }
+
+  if (isFlatpak()) {
+    if (hostHasCommand(program)) {
+      return { command: 'flatpak-spawn', args: ['--host', program], viaHost: true };
+    }
+    throw new GalaxiError(
+      `${program} was not found inside the Flatpak sandbox or on the host. ` +
+        `Install ${program} on the host system or use the bundled runtime.`,
+      GalaxiErrorType.LaunchError
+    );
+  }
+
+  throw new GalaxiError(`${program} was not found in PATH`, GalaxiErrorType.LaunchError);
+}
+
+/**
+ * Spawn a tool resolved with resolveTool. When the tool runs on the host,
+ * the variables that differ from our own environment and the working
+ * directory are forwarded explicitly, since flatpak-spawn doesn't inherit them.
+ */
+export function spawnTool(
+  program: string,
+  args: string[],
+  options: child_process.SpawnOptions = {}
+): child_process.ChildProcess {
+  const tool = resolveTool(program);
+  if (!tool.viaHost) {
+    return child_process.spawn(tool.command, [...tool.args, ...args], options);
+  }
+
+  const forwarded: string[] = [];
+  if (options.cwd) {
+    forwarded.push(`--directory=${options.cwd}`);
+  }
+  for (const [key, value] of Object.entries(options.env || {})) {
+    if (value !== undefined && process.env[key] !== value) {
+      forwarded.push(`--env=${key}=${value}`);
+    }
+  }
+
+  // flatpak-spawn expects its own options before the host command
+  const [hostFlag, hostProgram] = tool.args;
+  return child_process.spawn(tool.command, [hostFlag, ...forwarded, hostProgram, ...args], options);
+}
+
+// Value of the compatibility_tool setting that selects plain Wine
+export const COMPAT_TOOL_WINE = 'wine';
+
+/**
+ * WINEDEBUG value for wine processes: the chosen channels (or everything)
+ * with debugging on, silence otherwise
+ */
+export function wineDebugValue(debug: boolean, channels?: string): string {
+  if (!debug) {
+    return '-all';
+  }
+  return channels?.trim() || '+all';
+}
+
+/**
+ * Command used to run Windows programs: either a wine binary, or a Proton
+ * install invoked through its `proton run` entrypoint.
+ */
+export interface WindowsRunner {
+  command: string;
+  args: string[];
+  env: Record<string, string>;
+  isProton: boolean;
+}
+
+/**
+ * Locate the `proton` script for a compatibility tool setting, which may be
+ * the script itself or the Proton directory containing it.
+ */
+export function resolveProtonScript(tool: string): string {
+  const script = path.basename(tool) === 'proton' ? tool : path.join(tool, 'proton');
+  if (!fs.existsSync(script)) {
+    throw new GalaxiError(`Proton not found at ${script}`, GalaxiErrorType.LaunchError);
+  }
+  return script;
+}
+
+/**
+ * Build the runner for a Windows program using the given prefix.
+ *
+ * Proton keeps its prefix in `$STEAM_COMPAT_DATA_PATH/pfx`, so the compat
+ * data directory sits next to the Wine prefix with `pfx` linked to it. That
+ * way drive_c stays where the launcher and file checks expect it. With
+ * `prepare` off that directory isn't created, for a side-effect free look at
+ * the command.
+ */
+export function windowsRunner(
+  compatibilityTool: string | undefined,
+  wineExecutable: string,
+  winePrefix: string,
+  prepare: boolean = true
+): WindowsRunner {
+  if (!compatibilityTool || compatibilityTool === COMPAT_TOOL_WINE) {
+    return {
+      command: wineExecutable || 'wine',
+      args: [],
+      env: { WINEPREFIX: winePrefix },
+      isProton: false,
+    };
+  }
+
+  const script = resolveProtonScript(compatibilityTool);
+  const compatData = `${winePrefix}-proton`;
+  if (prepare) {
+    fs.mkdirSync(winePrefix, { recursive: true });
+    fs.mkdirSync(compatData, { recursive: true });
+    const pfx = path.join(compatData, 'pfx');
+    if (!fs.existsSync(pfx)) {
+      fs.symlinkSync(winePrefix, pfx);
+    }
+  }
+
+  // Proton only needs this to point at a Steam install for its client libraries
+  const steamDir = path.join(os.homedir(), '.steam', 'steam');
+  return {
+    command: script,
+    args: ['run'],
+    env: {
+      STEAM_COMPAT_DATA_PATH: compatData,
+      STEAM_COMPAT_CLIENT_INSTALL_PATH: fs.existsSync(steamDir) ? steamDir : compatData,
+    },
+    isProton: true,
+  };
+}
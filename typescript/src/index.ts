@@ -9,5 +9,6 @@ export * from './api/account';
 export * from './api/download';
 export * from './api/installer';
 export * from './api/launcher';
+export * from './api/tools';
 // Don't export database to avoid conflicts
 // export * from './api/database';
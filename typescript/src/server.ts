@@ -3,6 +3,11 @@ import * as url from 'url';
 import * as simple from './api/simple';
 
 const PORT = 3000;
+const DOWNLOAD_EVENTS_PATH = '/events/downloads';
+
+// Open download event streams, ended on shutdown as they would otherwise
+// keep server.close from ever completing
+const eventStreams = new Set<http.ServerResponse>();
 
 interface ApiRequest {
   method: string;
@@ -28,6 +33,26 @@ const server = http.createServer(async (req, res) => {
     return;
   }
 
+  // Server-sent events with every download progress change, see
+  // downloadProgressStream. Closing the connection stops the events.
+  if (req.method === 'GET' && req.url === DOWNLOAD_EVENTS_PATH) {
+    res.writeHead(200, {
+      'Content-Type': 'text/event-stream',
+      'Cache-Control': 'no-cache',
+      Connection: 'keep-alive',
+    });
+    res.write(': connected\n\n');
+    const unsubscribe = simple.downloadProgressStream(progress => {
+      res.write(`data: ${JSON.stringify(progress)}\n\n`);
+    });
+    eventStreams.add(res);
+    req.on('close', () => {
+      unsubscribe();
+      eventStreams.delete(res);
+    });
+    return;
+  }
+
   if (req.method === 'POST') {
     let body = '';
     req.on('data', chunk => {
@@ -75,23 +100,80 @@ async function handleApiCall(request: ApiRequest): Promise<any> {
   }
 }
 
-server.listen(PORT, () => {
-  console.log(`Galaxi backend server running on port ${PORT}`);
-});
+/**
+ * Whether a failed fetch means nothing is listening. Node's fetch wraps the
+ * socket error as `cause` with code ECONNREFUSED, Bun rejects with
+ * code ConnectionRefused directly.
+ */
+function isConnectionRefused(error: any): boolean {
+  const codes = [error?.code, error?.cause?.code];
+  return codes.includes('ECONNREFUSED') || codes.includes('ConnectionRefused');
+}
 
-// Handle graceful shutdown
-process.on('SIGINT', () => {
-  console.log('\nShutting down backend server...');
-  server.close(() => {
-    console.log('Server closed');
-    process.exit(0);
+/**
+ * `--launch <game id>`, used by the .desktop entries created for games. The
+ * launch goes through the running backend when there is one, so playtime is
+ * tracked as usual; otherwise the game is started directly and this process
+ * stays until it exits.
+ */
+async function launchFromCommandLine(gameId: number): Promise<void> {
+  try {
+    const response = await fetch(`http://localhost:${PORT}`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ method: 'launchGameById', params: [gameId] }),
+    });
+    const body = await response.json() as ApiResponse;
+    if (!body.success) {
+      throw new Error(body.error);
+    }
+    return;
+  } catch (error: any) {
+    if (!isConnectionRefused(error)) {
+      throw error;
+    }
+  }
+
+  const result = await simple.launchGameById(gameId);
+  if (!result.success) {
+    throw new Error(result.error_message);
+  }
+  // Nothing else keeps this process alive; stay until the game exits so
+  // its playtime is recorded
+  await simple.waitForGameExit(gameId);
+  await simple.shutdown();
+}
+
+const launchIndex = process.argv.indexOf('--launch');
+if (launchIndex !== -1) {
+  launchFromCommandLine(parseInt(process.argv[launchIndex + 1], 10)).catch(error => {
+    console.error(`Failed to launch game: ${error.message}`);
+    process.exit(1);
   });
-});
+} else {
+  server.listen(PORT, () => {
+    console.log(`Galaxi backend server running on port ${PORT}`);
+    simple.recoverPreviousRun();
+  });
+}
 
-process.on('SIGTERM', () => {
+// Handle graceful shutdown
+async function handleSignal(): Promise<void> {
   console.log('\nShutting down backend server...');
+  try {
+    await simple.shutdown();
+  } catch (error) {
+    console.error('Shutdown failed:', error);
+  }
+  for (const stream of eventStreams) {
+    stream.end();
+  }
+  eventStreams.clear();
   server.close(() => {
     console.log('Server closed');
     process.exit(0);
   });
-});
+}
+
+process.on('SIGINT', handleSignal);
+process.on('SIGTERM', handleSignal);